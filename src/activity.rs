@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+
+use crate::fivem::Player;
+
+#[derive(Clone)]
+pub struct Session {
+    pub name: String,
+    pub server_id: u32,
+    pub discord_id: Option<u64>,
+    pub joined_at: DateTime<Utc>,
+}
+
+pub struct ActivityChanges {
+    pub joined: Vec<Session>,
+    /// Sessions that ended, with their duration
+    pub left: Vec<(Session, TimeDelta)>,
+}
+
+/// Diffs consecutive player lists to find out who joined and who left the server
+#[derive(Default)]
+pub struct ActivityTracker {
+    /// Sessions by the stable identifier of the player, `None` until the first player list
+    sessions: Option<HashMap<String, Session>>,
+    /// When the last player list was received, players that left are counted as playing until then
+    last_update: Option<DateTime<Utc>>,
+}
+
+impl ActivityTracker {
+    /// Returns the changes since the last player list, the first list only sets the baseline
+    pub fn update(&mut self, players: &[Player]) -> Option<ActivityChanges> {
+        let now = Utc::now();
+        let last_update = self.last_update.replace(now).unwrap_or(now);
+        let first_update = self.sessions.is_none();
+        let sessions = self.sessions.get_or_insert_with(HashMap::new);
+
+        let mut current = HashMap::new();
+        let mut joined = Vec::new();
+
+        for player in players {
+            let session = match sessions.remove(&player.stable_identifier()) {
+                Some(session) => session,
+                None => {
+                    let session = Session {
+                        name: player.name.clone(),
+                        server_id: player.id,
+                        discord_id: player.discord_id(),
+                        joined_at: now,
+                    };
+
+                    joined.push(session.clone());
+                    session
+                }
+            };
+
+            current.insert(player.stable_identifier(), session);
+        }
+
+        let left = end_sessions(sessions, last_update);
+
+        *sessions = current;
+
+        if first_update || (joined.is_empty() && left.is_empty()) {
+            return None;
+        }
+
+        Some(ActivityChanges { joined, left })
+    }
+
+    /// Ends every session when the server goes offline, so the downtime isn't counted as playtime
+    pub fn end_sessions(&mut self) -> Option<ActivityChanges> {
+        let (Some(sessions), Some(last_update)) = (self.sessions.as_mut(), self.last_update) else {
+            return None;
+        };
+
+        let left = end_sessions(sessions, last_update);
+        if left.is_empty() {
+            return None;
+        }
+
+        Some(ActivityChanges {
+            joined: Vec::new(),
+            left,
+        })
+    }
+}
+
+fn end_sessions(
+    sessions: &mut HashMap<String, Session>,
+    ended_at: DateTime<Utc>,
+) -> Vec<(Session, TimeDelta)> {
+    sessions
+        .drain()
+        .map(|(_, session)| {
+            let duration = ended_at - session.joined_at;
+            (session, duration)
+        })
+        .collect()
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+/// Polling delay that doubles with every consecutive failure after the first one
+pub struct Backoff {
+    interval: Duration,
+    max_delay: Duration,
+    consecutive_failures: u32,
+}
+
+impl Backoff {
+    pub fn new(interval: Duration, max_delay: Duration) -> Self {
+        Self {
+            interval,
+            max_delay: max_delay.max(interval),
+            consecutive_failures: 0,
+        }
+    }
+
+    pub fn record(&mut self, success: bool) {
+        self.consecutive_failures = match success {
+            true => 0,
+            false => self.consecutive_failures.saturating_add(1),
+        };
+    }
+
+    /// The time to wait before the next poll
+    pub fn delay(&self) -> Duration {
+        let exponent = self.consecutive_failures.saturating_sub(1).min(31);
+
+        self.interval
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_delay)
+    }
+}
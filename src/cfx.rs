@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serenity::all::Color;
+use serenity::all::CreateEmbed;
+
+use crate::status::truncate;
+use crate::status::MAX_FIELD_LENGTH;
+use crate::BRAND_COLOR;
+use crate::ERROR_COLOR;
+use crate::SUCCESS_COLOR;
+use crate::WARNING_COLOR;
+
+/// Length of the latest update shown for every incident in the status message
+const MAX_UPDATE_LENGTH: usize = 200;
+
+#[derive(Deserialize)]
+pub struct CFXStatus {
+    /// The overall status of the page
+    pub status: Option<PageStatus>,
+    pub components: Box<[Component]>,
+    /// Unresolved incidents
+    #[serde(default)]
+    pub incidents: Vec<Incident>,
+    /// Upcoming and in progress maintenances
+    #[serde(default)]
+    pub scheduled_maintenances: Vec<Incident>,
+}
+
+#[derive(Deserialize)]
+pub struct PageStatus {
+    /// `none`, `minor`, `major`, `critical` or `maintenance`
+    pub indicator: String,
+}
+
+#[derive(Deserialize)]
+pub struct Component {
+    pub name: String,
+    pub status: String,
+}
+
+impl Component {
+    /// How bad the status of the component is, the worst one decides the color of the status message
+    fn severity(&self) -> u8 {
+        match self.status.as_str() {
+            "operational" => 0,
+            "under_maintenance" => 1,
+            "degraded_performance" => 2,
+            "partial_outage" => 3,
+            "major_outage" => 4,
+            _ => 2,
+        }
+    }
+
+    fn status_label(&self) -> &'static str {
+        match self.status.as_str() {
+            "operational" => "✔ Elérhető",
+            "degraded_performance" => "⚠️ Lassú működés",
+            "partial_outage" => "🟠 Részleges leállás",
+            "major_outage" => "❌ Nem elérhető",
+            "under_maintenance" => "🔧 Karbantartás",
+            _ => "❔ Ismeretlen",
+        }
+    }
+}
+
+/// Which components are shown in the status message
+pub struct ComponentFilter {
+    /// Only these are shown if not empty
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl ComponentFilter {
+    fn shows(&self, component: &Component) -> bool {
+        (self.include.is_empty() || self.include.contains(&component.name))
+            && !self.exclude.contains(&component.name)
+    }
+}
+
+/// An incident or a scheduled maintenance, statuspage uses the same shape for both
+#[derive(Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub impact: String,
+    pub shortlink: Option<String>,
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub scheduled_until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub incident_updates: Vec<IncidentUpdate>,
+}
+
+#[derive(Deserialize)]
+pub struct IncidentUpdate {
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Incident {
+    fn is_resolved(&self) -> bool {
+        matches!(
+            self.status.as_str(),
+            "resolved" | "postmortem" | "completed"
+        )
+    }
+
+    fn latest_update(&self) -> Option<&IncidentUpdate> {
+        self.incident_updates
+            .iter()
+            .max_by_key(|update| update.created_at)
+    }
+
+    fn status_label(&self) -> &str {
+        match self.status.as_str() {
+            "investigating" => "Vizsgálat alatt",
+            "identified" => "Azonosítva",
+            "monitoring" => "Megfigyelés alatt",
+            "resolved" => "Megoldva",
+            "postmortem" => "Utólagos elemzés",
+            "scheduled" => "Ütemezve",
+            "in_progress" => "Folyamatban",
+            "verifying" => "Ellenőrzés alatt",
+            "completed" => "Befejezve",
+            status => status,
+        }
+    }
+
+    fn impact_label(&self) -> &str {
+        match self.impact.as_str() {
+            "none" => "Nincs hatás",
+            "minor" => "Kisebb",
+            "major" => "Jelentős",
+            "critical" => "Kritikus",
+            "maintenance" => "Karbantartás",
+            impact => impact,
+        }
+    }
+
+    /// The name, impact, status and latest update of the incident
+    fn summary(&self) -> String {
+        let mut summary = format!(
+            "**{}**\n{} hatás | {}",
+            self.name,
+            self.impact_label(),
+            self.status_label()
+        );
+
+        if let (Some(scheduled_for), Some(scheduled_until)) =
+            (self.scheduled_for, self.scheduled_until)
+        {
+            summary += &format!(
+                "\n<t:{}:f> - <t:{}:f>",
+                scheduled_for.timestamp(),
+                scheduled_until.timestamp()
+            );
+        }
+
+        if let Some(update) = self.latest_update() {
+            summary += &format!(
+                "\n> {} (<t:{}:R>)",
+                truncate(update.body.trim(), MAX_UPDATE_LENGTH).replace('\n', " "),
+                update.created_at.timestamp()
+            );
+        }
+
+        summary
+    }
+}
+
+pub struct IncidentChanges<'a> {
+    pub new: Vec<&'a Incident>,
+    /// The ids and names of the incidents that were resolved
+    pub resolved: Vec<(String, String)>,
+}
+
+/// Compares the active incidents with the already announced ones, by id
+pub fn diff_incidents<'a>(
+    announced: &HashMap<String, String>,
+    incidents: &'a [Incident],
+) -> IncidentChanges<'a> {
+    let active = incidents
+        .iter()
+        .filter(|incident| !incident.is_resolved())
+        .collect::<Vec<_>>();
+
+    IncidentChanges {
+        new: active
+            .iter()
+            .filter(|incident| !announced.contains_key(&incident.id))
+            .copied()
+            .collect(),
+        resolved: announced
+            .iter()
+            .filter(|(id, _)| !active.iter().any(|incident| &incident.id == *id))
+            .map(|(id, name)| (id.clone(), name.clone()))
+            .collect(),
+    }
+}
+
+pub fn new_incident_embed(incident: &Incident) -> CreateEmbed {
+    let embed = CreateEmbed::new()
+        .title("CFX | Új Incidens")
+        .description(truncate(&incident.summary(), MAX_FIELD_LENGTH))
+        .color(WARNING_COLOR);
+
+    match &incident.shortlink {
+        Some(shortlink) => embed.url(shortlink),
+        None => embed,
+    }
+}
+
+pub fn resolved_incident_embed(name: &str) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("CFX | Incidens Megoldva")
+        .description(format!("**{}**\nAz incidens megoldódott!", name))
+        .color(SUCCESS_COLOR)
+}
+
+/// Lists the incidents in a single field, leaving out the ones that don't fit
+fn incident_list(incidents: &[&Incident]) -> String {
+    let mut list = String::new();
+
+    for incident in incidents {
+        let summary = incident.summary() + "\n\n";
+
+        if list.len() + summary.len() > MAX_FIELD_LENGTH {
+            if list.is_empty() {
+                list = truncate(&summary, MAX_FIELD_LENGTH).to_string();
+            }
+
+            break;
+        }
+
+        list += &summary;
+    }
+
+    list
+}
+
+/// Adds the global status and the status of every shown component to the CFX status message
+pub fn add_cfx_status_fields(
+    mut embed: CreateEmbed,
+    response: &Result<CFXStatus>,
+    filter: &ComponentFilter,
+) -> CreateEmbed {
+    match response {
+        Ok(status) => {
+            let global_status = match status.status.as_ref().map(|s| s.indicator.as_str()) {
+                Some("none") | None => "✅ Elérhető",
+                Some("minor") => "⚠️ Kisebb zavar",
+                Some("major") => "🟠 Jelentős zavar",
+                Some("critical") => "❌ Kritikus leállás",
+                Some("maintenance") => "🔧 Karbantartás",
+                Some(_) => "❔ Ismeretlen",
+            };
+            embed = embed.fields(vec![("Globális Státusz:", global_status, false)]);
+
+            let components = status
+                .components
+                .iter()
+                .filter(|c| filter.shows(c))
+                .collect::<Vec<_>>();
+
+            embed = embed.fields(components.iter().map(|c| (&c.name, c.status_label(), true)));
+
+            let color = match components.iter().map(|c| c.severity()).max() {
+                Some(4) => ERROR_COLOR,
+                Some(3) => Color::ORANGE,
+                Some(2) => Color::GOLD,
+                Some(1) => BRAND_COLOR,
+                _ => SUCCESS_COLOR,
+            };
+            embed = embed.color(color);
+
+            let incidents = status
+                .incidents
+                .iter()
+                .filter(|incident| !incident.is_resolved())
+                .collect::<Vec<_>>();
+            if !incidents.is_empty() {
+                embed = embed.field("Aktív Incidensek:", incident_list(&incidents), false);
+            }
+
+            let maintenances = status
+                .scheduled_maintenances
+                .iter()
+                .filter(|maintenance| !maintenance.is_resolved())
+                .collect::<Vec<_>>();
+            if !maintenances.is_empty() {
+                embed = embed.field(
+                    "Ütemezett Karbantartások:",
+                    incident_list(&maintenances),
+                    false,
+                );
+            }
+        }
+        Err(_) => {
+            embed = embed
+                .fields(vec![("Globális Státusz:", "❌ Nem elérhető", true)])
+                .color(ERROR_COLOR);
+        }
+    }
+
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::InMemoryStatusProvider;
+    use crate::provider::StatusProvider;
+    use crate::utils::embed_fields;
+
+    fn default_filter() -> ComponentFilter {
+        ComponentFilter {
+            include: Vec::new(),
+            exclude: ["Server List Frontend", "RedM", "\"Runtime\"", "IDMS"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_components() {
+        let provider = InMemoryStatusProvider::default()
+            .with_cfx_summary(include_str!("../tests/fixtures/summary.json"));
+
+        let response = provider.get_cfx_status().await;
+        let fields = embed_fields(&add_cfx_status_fields(
+            CreateEmbed::new(),
+            &response,
+            &default_filter(),
+        ));
+
+        assert_eq!(
+            fields[..5],
+            [
+                ("Globális Státusz:".into(), "⚠️ Kisebb zavar".into()),
+                ("CnL".into(), "✔ Elérhető".into()),
+                ("Policy".into(), "⚠️ Lassú működés".into()),
+                ("Keymaster".into(), "✔ Elérhető".into()),
+                ("Forums".into(), "✔ Elérhető".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn colors_by_the_worst_shown_component() {
+        let provider = InMemoryStatusProvider::default()
+            .with_cfx_summary(include_str!("../tests/fixtures/summary.json"));
+        let response = provider.get_cfx_status().await;
+
+        let color = |filter: &ComponentFilter| {
+            serde_json::to_value(add_cfx_status_fields(CreateEmbed::new(), &response, filter))
+                .unwrap()["color"]
+                .clone()
+        };
+
+        // Policy is degraded
+        assert_eq!(color(&default_filter()), Color::GOLD.0);
+        // RedM has a major outage
+        assert_eq!(
+            color(&ComponentFilter {
+                include: Vec::new(),
+                exclude: Vec::new(),
+            }),
+            ERROR_COLOR.0
+        );
+
+        let include_only = ComponentFilter {
+            include: vec![String::from("CnL"), String::from("Keymaster")],
+            exclude: Vec::new(),
+        };
+        assert_eq!(color(&include_only), SUCCESS_COLOR.0);
+        assert_eq!(
+            embed_fields(&add_cfx_status_fields(
+                CreateEmbed::new(),
+                &response,
+                &include_only
+            ))
+            .len(),
+            5
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_incidents_and_maintenances() {
+        let provider = InMemoryStatusProvider::default()
+            .with_cfx_summary(include_str!("../tests/fixtures/summary.json"));
+
+        let response = provider.get_cfx_status().await;
+        let fields = embed_fields(&add_cfx_status_fields(
+            CreateEmbed::new(),
+            &response,
+            &default_filter(),
+        ));
+
+        let (name, incidents) = &fields[5];
+        assert_eq!(name, "Aktív Incidensek:");
+        assert!(incidents.starts_with(
+            "**Policy service returning errors**\nKisebb hatás | Azonosítva\n> We have identified"
+        ));
+
+        let (name, maintenances) = &fields[6];
+        assert_eq!(name, "Ütemezett Karbantartások:");
+        assert!(maintenances.contains("Karbantartás hatás | Ütemezve"));
+        assert!(maintenances.contains("<t:1720058400:f> - <t:1720065600:f>"));
+    }
+
+    #[tokio::test]
+    async fn diffs_announced_incidents() {
+        let provider = InMemoryStatusProvider::default()
+            .with_cfx_summary(include_str!("../tests/fixtures/summary.json"));
+        let status = provider.get_cfx_status().await.unwrap();
+
+        let changes = diff_incidents(&HashMap::new(), &status.incidents);
+        assert_eq!(changes.new.len(), 1);
+        assert_eq!(changes.new[0].id, "k4f8s2d9g1h7");
+        assert!(changes.resolved.is_empty());
+
+        let announced = HashMap::from([
+            ("k4f8s2d9g1h7".to_string(), "Policy service".to_string()),
+            ("old".to_string(), "Old incident".to_string()),
+        ]);
+        let changes = diff_incidents(&announced, &status.incidents);
+        assert!(changes.new.is_empty());
+        assert_eq!(
+            changes.resolved,
+            vec![("old".to_string(), "Old incident".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_unreachable_status_page() {
+        let response = InMemoryStatusProvider::default().get_cfx_status().await;
+        let embed = add_cfx_status_fields(CreateEmbed::new(), &response, &default_filter());
+
+        assert_eq!(
+            embed_fields(&embed),
+            vec![("Globális Státusz:".into(), "❌ Nem elérhető".into())]
+        );
+        assert_eq!(
+            serde_json::to_value(&embed).unwrap()["color"],
+            ERROR_COLOR.0
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::bail;
+use anyhow::Result;
+use plotters::prelude::*;
+
+use crate::history::PlayerSample;
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 400;
+
+const BACKGROUND_COLOR: RGBColor = RGBColor(43, 45, 49);
+const GRID_COLOR: RGBColor = RGBColor(64, 66, 73);
+const PLAYERS_COLOR: RGBColor = RGBColor(33, 121, 227);
+const MAX_PLAYERS_COLOR: RGBColor = RGBColor(227, 46, 36);
+
+/// Renders the player count (blue) and the player limit (red) as a PNG line chart.
+///
+/// No fonts are bundled, so the chart has no labels, the embed it is attached to carries the numbers.
+pub fn render_player_chart(samples: &[PlayerSample]) -> Result<Vec<u8>> {
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        bail!("No samples to render");
+    };
+
+    let y_max = samples
+        .iter()
+        .map(|sample| sample.players.max(sample.max_players))
+        .max()
+        .unwrap_or(0);
+    // Leave some room above the highest line
+    let y_max = y_max + y_max / 10 + 1;
+    // A single sample would make an empty x range
+    let x_range = first.timestamp..last.timestamp.max(first.timestamp + 1);
+
+    let mut buffer = vec![0; (WIDTH * HEIGHT * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&BACKGROUND_COLOR)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(20)
+            .build_cartesian_2d(x_range, 0..y_max)?;
+
+        // Without fonts the mesh only draws the grid lines
+        chart
+            .configure_mesh()
+            .light_line_style(TRANSPARENT)
+            .bold_line_style(GRID_COLOR)
+            .axis_style(GRID_COLOR)
+            .draw()?;
+
+        chart.draw_series(LineSeries::new(
+            samples
+                .iter()
+                .map(|sample| (sample.timestamp, sample.max_players)),
+            MAX_PLAYERS_COLOR.stroke_width(2),
+        ))?;
+
+        chart.draw_series(LineSeries::new(
+            samples
+                .iter()
+                .map(|sample| (sample.timestamp, sample.players)),
+            PLAYERS_COLOR.stroke_width(3),
+        ))?;
+
+        root.present()?;
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&buffer)?;
+
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_png() {
+        let samples: Vec<PlayerSample> = (0..5)
+            .map(|i| PlayerSample {
+                timestamp: 1_700_000_000 + i * 60,
+                players: 10 + i as u32 * 5,
+                max_players: 64,
+            })
+            .collect();
+
+        let png = render_player_chart(&samples).unwrap();
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((frame.width, frame.height), (WIDTH, HEIGHT));
+
+        // The player line is drawn over the background
+        assert!(pixels
+            .chunks(3)
+            .any(|pixel| pixel == [PLAYERS_COLOR.0, PLAYERS_COLOR.1, PLAYERS_COLOR.2]));
+
+        assert!(render_player_chart(&[]).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::Utc;
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{Context, Error},
+    status::{truncate, MAX_FIELD_LENGTH},
+    supervisor::Supervisor,
+    utils::{format_duration, send_reply},
+    BRAND_COLOR, BRAND_NAME_SHORT, WARNING_COLOR,
+};
+
+/// Health of the background jobs of the bot
+#[poise::command(slash_command, default_member_permissions = "MANAGE_GUILD")]
+pub async fn health(ctx: Context<'_>) -> Result<(), Error> {
+    let jobs = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let supervisor = client_data.get::<Supervisor>().unwrap().clone();
+        supervisor
+    }
+    .health()
+    .await;
+
+    let now = Utc::now();
+
+    let fields = jobs
+        .iter()
+        .map(|(name, health)| {
+            let mut value = match health.running {
+                true => format!("✅ Fut ({})", format_duration(now - health.started_at)),
+                false => String::from("❌ Leállt"),
+            };
+
+            value += &format!("\nÚjraindítások: **{}**", health.restarts);
+
+            if let Some(last_error) = &health.last_error {
+                value += &format!("\nUtolsó hiba: `{}`", last_error);
+            }
+
+            (name, truncate(&value, MAX_FIELD_LENGTH).to_string(), false)
+        })
+        .collect::<Vec<_>>();
+
+    let color = match jobs.values().all(|health| health.running) {
+        true => BRAND_COLOR,
+        false => WARNING_COLOR,
+    };
+
+    send_reply(
+        &ctx,
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title("Háttérfolyamatok")
+                    .fields(fields)
+                    .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                    .timestamp(Timestamp::now())
+                    .color(color),
+            )
+            .ephemeral(true),
+    )
+    .await;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use chrono::{TimeDelta, Utc};
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, Context, Error},
+    playtime::PlaytimeStore,
+    status::sanitize_name,
+    utils::{format_duration, send_reply},
+    BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(poise::ChoiceParameter)]
+pub enum LeaderboardPeriod {
+    #[name = "week"]
+    Week,
+    #[name = "month"]
+    Month,
+    #[name = "all"]
+    All,
+}
+
+/// Leaderboards of the server
+#[poise::command(slash_command, subcommands("leaderboard_playtime"))]
+pub async fn leaderboard(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// The players with the most playtime
+#[poise::command(slash_command, rename = "playtime")]
+pub async fn leaderboard_playtime(
+    ctx: Context<'_>,
+    #[description = "The period to rank (default: week)"] period: Option<LeaderboardPeriod>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    let period = period.unwrap_or(LeaderboardPeriod::Week);
+    let period_name = match period {
+        LeaderboardPeriod::Week => "utolsó 7 nap",
+        LeaderboardPeriod::Month => "utolsó 30 nap",
+        LeaderboardPeriod::All => "összesen",
+    };
+
+    let now = Utc::now();
+
+    let mut ranking = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let playtime_store = client_data.get::<PlaytimeStore>().unwrap().read().await;
+
+        playtime_store
+            .players(&server.name)
+            .map(|playtime| {
+                let seconds = match period {
+                    LeaderboardPeriod::Week => playtime.seconds_in_last_days(7, now),
+                    LeaderboardPeriod::Month => playtime.seconds_in_last_days(30, now),
+                    LeaderboardPeriod::All => playtime.total_seconds,
+                };
+
+                let name = match playtime.discord_id {
+                    Some(discord_id) => {
+                        format!("{} (<@{}>)", sanitize_name(&playtime.name), discord_id)
+                    }
+                    None => sanitize_name(&playtime.name),
+                };
+
+                (name, seconds)
+            })
+            .filter(|(_, seconds)| *seconds > 0)
+            .collect::<Vec<_>>()
+    };
+    ranking.sort_by_key(|(_, seconds)| -seconds);
+
+    let description = if ranking.is_empty() {
+        String::from("Még nincs adat.")
+    } else {
+        ranking
+            .iter()
+            .take(LEADERBOARD_SIZE)
+            .enumerate()
+            .map(|(i, (name, seconds))| {
+                let place = match i {
+                    0 => String::from("🥇"),
+                    1 => String::from("🥈"),
+                    2 => String::from("🥉"),
+                    _ => format!("**{}.**", i + 1),
+                };
+
+                format!(
+                    "{} {} - {}",
+                    place,
+                    name,
+                    format_duration(TimeDelta::seconds(*seconds))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    send_reply(
+        &ctx,
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title(format!(
+                    "{} | Játékidő Toplista ({})",
+                    server.name, period_name
+                ))
+                .description(description)
+                .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                .timestamp(Timestamp::now())
+                .color(BRAND_COLOR),
+        ),
+    )
+    .await;
+
+    Ok(())
+}
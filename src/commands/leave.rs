@@ -2,6 +2,7 @@ use poise::CreateReply;
 
 use crate::{
     commands::{Context, Error},
+    music::update_music_panel,
     utils::send_reply,
 };
 
@@ -19,6 +20,9 @@ pub async fn leave(ctx: Context<'_>) -> Result<(), Error> {
     if has_handler {
         match manager.remove(guild_id).await {
             Ok(_) => {
+                update_music_panel(ctx.http(), &ctx.serenity_context().data, &manager, guild_id)
+                    .await;
+
                 send_reply(&ctx, CreateReply::default().content("Left voice channel")).await;
             }
             Err(e) => {
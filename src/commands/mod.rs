@@ -1,11 +1,70 @@
+use std::sync::Arc;
+
+use poise::CreateReply;
 use reqwest::Client as HttpClient;
 
+use crate::{
+    config::FivemServer,
+    provider::{StatusProvider, StatusProviderKey},
+    utils::send_reply,
+    ClientData,
+};
+
 pub struct Data {
     pub http_client: HttpClient,
 }
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Context<'a> = poise::Context<'a, Data, Error>;
 
+/// Suggests the names of the configured FiveM servers
+pub async fn autocomplete_server(ctx: Context<'_>, partial: &str) -> Vec<String> {
+    let client_data = ctx.serenity_context().data.read().await;
+    let (_, config) = client_data.get::<ClientData>().unwrap();
+
+    let partial = partial.to_lowercase();
+
+    let names = config
+        .read()
+        .await
+        .fivem_servers
+        .iter()
+        .map(|server| server.name.clone())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .collect();
+    names
+}
+
+pub async fn status_provider(ctx: &Context<'_>) -> Arc<dyn StatusProvider> {
+    let client_data = ctx.serenity_context().data.read().await;
+    client_data.get::<StatusProviderKey>().unwrap().clone()
+}
+
+/// Finds the server chosen in a command, defaulting to the main server, and replies if there is no such server
+pub async fn resolve_server(ctx: &Context<'_>, name: Option<&str>) -> Option<FivemServer> {
+    let server = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let (_, config) = client_data.get::<ClientData>().unwrap();
+        let locked_config = config.read().await;
+
+        match name {
+            Some(name) => locked_config.server(name).cloned(),
+            None => Some(locked_config.main_server().clone()),
+        }
+    };
+
+    if server.is_none() {
+        send_reply(
+            ctx,
+            CreateReply::default()
+                .content("Nincs ilyen szerver!")
+                .ephemeral(true),
+        )
+        .await;
+    }
+
+    server
+}
+
 mod ping;
 pub use ping::ping;
 mod play;
@@ -18,3 +77,27 @@ mod pause;
 pub use pause::pause;
 mod resume;
 pub use resume::resume;
+mod musicpanel;
+pub use musicpanel::musicpanel;
+mod stats;
+pub use stats::stats;
+mod uptime;
+pub use uptime::uptime;
+mod status;
+pub use status::status;
+mod serverinfo;
+pub use serverinfo::serverinfo;
+mod health;
+pub use health::health;
+mod players;
+pub use players::players;
+mod whois;
+pub use whois::whois;
+mod online;
+pub use online::online;
+mod playtime;
+pub use playtime::playtime;
+mod leaderboard;
+pub use leaderboard::leaderboard;
+mod suggestion;
+pub use suggestion::suggestion;
@@ -0,0 +1,58 @@
+use poise::CreateReply;
+use serenity::all::CreateMessage;
+use tracing::info;
+
+use crate::{
+    commands::{Context, Error},
+    music::create_music_panel,
+    utils::{send_message, send_reply},
+    ClientData,
+};
+
+/// Post the music control panel in this channel
+#[poise::command(slash_command, default_member_permissions = "MANAGE_GUILD")]
+pub async fn musicpanel(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().expect("Expected to be in a guild");
+
+    let manager = songbird::get(ctx.serenity_context())
+        .await
+        .expect("Expected songbird voice client to be placed in at initialization");
+
+    let (embed, components) = create_music_panel(manager.get(guild_id)).await;
+    let message = CreateMessage::new().embed(embed).components(components);
+
+    let Some(panel_message) = send_message(ctx.http(), ctx.channel_id(), message).await else {
+        send_reply(
+            &ctx,
+            CreateReply::default()
+                .content("Failed to post the music panel")
+                .ephemeral(true),
+        )
+        .await;
+        return Ok(());
+    };
+
+    let client_data = ctx.serenity_context().data.read().await;
+    let (_, config) = client_data.get::<ClientData>().unwrap();
+
+    let mut locked_config = config.write().await;
+    locked_config
+        .data_json
+        .set_music_panel(u64::from(ctx.channel_id()), u64::from(panel_message.id));
+    locked_config.data_json.save();
+
+    info!(
+        "Created new music panel message with id: {}",
+        panel_message.id.to_string()
+    );
+
+    send_reply(
+        &ctx,
+        CreateReply::default()
+            .content("Music panel posted")
+            .ephemeral(true),
+    )
+    .await;
+
+    Ok(())
+}
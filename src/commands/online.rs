@@ -0,0 +1,79 @@
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Member, Timestamp};
+
+use crate::{
+    commands::{status_provider, Context, Error},
+    status::sanitize_name,
+    utils::send_reply,
+    ClientData, BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+/// Check whether a member is playing on any of the servers
+#[poise::command(slash_command)]
+pub async fn online(
+    ctx: Context<'_>,
+    #[description = "The member to look for"] member: Member,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let servers = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let (_, config) = client_data.get::<ClientData>().unwrap();
+        let servers = config.read().await.fivem_servers.clone();
+        servers
+    };
+
+    let member_id = u64::from(member.user.id);
+    let status_provider = status_provider(&ctx).await;
+    let mut sessions = Vec::new();
+
+    for server in &servers {
+        let Ok((players, _)) = status_provider.get_players(&server.endpoint).await else {
+            continue;
+        };
+
+        sessions.extend(
+            players
+                .iter()
+                .filter(|player| player.discord_id() == Some(member_id))
+                .map(|player| {
+                    format!(
+                        "**{}:** {} *(Szerver ID: {})*",
+                        server.name,
+                        sanitize_name(&player.name),
+                        player.id
+                    )
+                }),
+        );
+    }
+
+    let description = if sessions.is_empty() {
+        format!(
+            "❌ <@{}> jelenleg nincs fent egyik szerveren sem.",
+            member_id
+        )
+    } else {
+        format!(
+            "✅ <@{}> jelenleg fent van:\n{}",
+            member_id,
+            sessions.join("\n")
+        )
+    };
+
+    send_reply(
+        &ctx,
+        CreateReply::default()
+            .embed(
+                CreateEmbed::new()
+                    .title("Online Státusz")
+                    .description(description)
+                    .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                    .timestamp(Timestamp::now())
+                    .color(BRAND_COLOR),
+            )
+            .ephemeral(true),
+    )
+    .await;
+
+    Ok(())
+}
@@ -6,12 +6,15 @@ use serenity::{
     async_trait,
 };
 use songbird::{
-    input::YoutubeDl, Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent,
+    input::{Compose, YoutubeDl},
+    tracks::Track,
+    Event, EventContext, EventHandler as VoiceEventHandler, TrackEvent,
 };
 use tracing::{error, info};
 
 use crate::{
     commands::{Context, Error},
+    music::{update_music_panel, TrackChangeNotifier, TrackTitle},
     utils::send_reply,
 };
 
@@ -58,19 +61,48 @@ pub async fn play(
         .await
         .expect("Expected songbird voice client to be placed in at initialization");
 
+    let mut src = if do_search {
+        YoutubeDl::new_search(http_client, url)
+    } else {
+        YoutubeDl::new(http_client, url)
+    };
+
+    // Resolving the title runs `yt-dlp`, which can take longer than the interaction deadline
+    ctx.defer().await?;
+
+    // Resolve the title before locking the call, so the panel and other commands aren't blocked
+    let title = match src.aux_metadata().await {
+        Ok(metadata) => metadata.title,
+        Err(e) => {
+            error!("Failed to get track metadata: {e:?}");
+            None
+        }
+    };
+
+    // Global events would be registered again on every `/play` if the call already exists
+    let is_new_call = manager.get(guild_id).is_none();
+
     match manager.join(guild_id, channel_id).await {
         Ok(handler_lock) => {
             let mut handler = handler_lock.lock().await;
 
-            handler.add_global_event(TrackEvent::Error.into(), TrackErrorNotifier);
+            if is_new_call {
+                handler.add_global_event(TrackEvent::Error.into(), TrackErrorNotifier);
+
+                for event in [TrackEvent::Play, TrackEvent::End] {
+                    handler.add_global_event(
+                        event.into(),
+                        TrackChangeNotifier {
+                            http: ctx.serenity_context().http.clone(),
+                            data: ctx.serenity_context().data.clone(),
+                            manager: manager.clone(),
+                            guild_id,
+                        },
+                    );
+                }
+            }
             handler.deafen(true).await.ok();
 
-            let src = if do_search {
-                YoutubeDl::new_search(http_client, url)
-            } else {
-                YoutubeDl::new(http_client, url)
-            };
-
             send_reply(
                 &ctx,
                 CreateReply::default().content(format!(
@@ -80,7 +112,20 @@ pub async fn play(
             )
             .await;
 
-            handler.enqueue_with_preload(src.into(), Some(Duration::from_secs(10)));
+            let track_handle =
+                handler.enqueue_with_preload(Track::from(src), Some(Duration::from_secs(10)));
+
+            if let Some(title) = title {
+                track_handle
+                    .typemap()
+                    .write()
+                    .await
+                    .insert::<TrackTitle>(title);
+            }
+
+            // The panel may have been rendered by the `Play` event before the title was stored
+            drop(handler);
+            update_music_panel(ctx.http(), &ctx.serenity_context().data, &manager, guild_id).await;
 
             Ok(())
         }
@@ -0,0 +1,134 @@
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, status_provider, Context, Error},
+    status::sanitize_name,
+    utils::{paginate_embeds, send_reply},
+    BRAND_COLOR, BRAND_NAME_SHORT, ERROR_COLOR,
+};
+
+const PLAYERS_PER_PAGE: usize = 20;
+
+/// List the players on the server
+#[poise::command(slash_command)]
+pub async fn players(
+    ctx: Context<'_>,
+    #[description = "Filter by name or server id"] filter: Option<String>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let title = format!("{} | Játékosok", server.name);
+
+    let (players, server_info) = match status_provider(&ctx)
+        .await
+        .get_players(&server.endpoint)
+        .await
+    {
+        Ok(response) => response,
+        Err(_) => {
+            send_reply(
+                &ctx,
+                CreateReply::default()
+                    .embed(
+                        CreateEmbed::new()
+                            .title(title)
+                            .description("❌ A szerver nem elérhető!")
+                            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                            .timestamp(Timestamp::now())
+                            .color(ERROR_COLOR),
+                    )
+                    .ephemeral(true),
+            )
+            .await;
+            return Ok(());
+        }
+    };
+
+    let filter = filter.map(|filter| filter.to_lowercase());
+
+    let mut lines = players
+        .iter()
+        .filter(|player| match &filter {
+            Some(filter) => {
+                player.name.to_lowercase().contains(filter)
+                    || player.id.to_string().contains(filter)
+            }
+            None => true,
+        })
+        .map(|player| {
+            (
+                player.id,
+                match player.discord_id() {
+                    Some(discord_id) => format!(
+                        "`#{}` {} (<@{}>) *({}ms)*",
+                        player.id,
+                        sanitize_name(&player.name),
+                        discord_id,
+                        player.ping
+                    ),
+                    None => format!(
+                        "`#{}` {} *({}ms)*",
+                        player.id,
+                        sanitize_name(&player.name),
+                        player.ping
+                    ),
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    lines.sort_by_key(|(id, _)| *id);
+
+    let header = format!(
+        "**Elérhető Játékosok:** {}/{}\n\n",
+        players.len(),
+        server_info.vars.max_players
+    );
+
+    let pages = if lines.is_empty() {
+        vec![format!("{}Nincs találat.", header)]
+    } else {
+        lines
+            .chunks(PLAYERS_PER_PAGE)
+            .map(|chunk| {
+                header.clone()
+                    + &chunk
+                        .iter()
+                        .map(|(_, line)| line.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+            })
+            .collect()
+    };
+
+    let page_count = pages.len();
+
+    let embeds = pages
+        .into_iter()
+        .enumerate()
+        .map(|(i, page)| {
+            CreateEmbed::new()
+                .title(&title)
+                .description(page)
+                .footer(CreateEmbedFooter::new(format!(
+                    "{} • {}/{}",
+                    BRAND_NAME_SHORT,
+                    i + 1,
+                    page_count
+                )))
+                .timestamp(Timestamp::now())
+                .color(BRAND_COLOR)
+        })
+        .collect();
+
+    paginate_embeds(&ctx, embeds).await?;
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use chrono::{TimeDelta, Utc};
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Member, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, Context, Error},
+    playtime::PlaytimeStore,
+    utils::{format_duration, send_reply},
+    BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+/// Show how much time a member has played on the server
+#[poise::command(slash_command)]
+pub async fn playtime(
+    ctx: Context<'_>,
+    #[description = "The member to show (default: you)"] member: Option<Member>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    let user_id = u64::from(match &member {
+        Some(member) => member.user.id,
+        None => ctx.author().id,
+    });
+
+    let now = Utc::now();
+
+    // A member can have more than one FiveM account linked
+    let (total, week, month, accounts) = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let playtime_store = client_data.get::<PlaytimeStore>().unwrap().read().await;
+
+        playtime_store
+            .players(&server.name)
+            .filter(|playtime| playtime.discord_id == Some(user_id))
+            .fold((0, 0, 0, 0), |(total, week, month, accounts), playtime| {
+                (
+                    total + playtime.total_seconds,
+                    week + playtime.seconds_in_last_days(7, now),
+                    month + playtime.seconds_in_last_days(30, now),
+                    accounts + 1,
+                )
+            })
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} | Játékidő", server.name))
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    let embed = if accounts == 0 {
+        embed.description(format!(
+            "<@{}> még nem játszott a szerveren, vagy nincs összekapcsolva a Discord fiókja.",
+            user_id
+        ))
+    } else {
+        embed
+            .description(format!("<@{}> játékideje", user_id))
+            .fields(vec![
+                (
+                    "Utolsó 7 nap:",
+                    format_duration(TimeDelta::seconds(week)),
+                    true,
+                ),
+                (
+                    "Utolsó 30 nap:",
+                    format_duration(TimeDelta::seconds(month)),
+                    true,
+                ),
+                (
+                    "Összesen:",
+                    format_duration(TimeDelta::seconds(total)),
+                    true,
+                ),
+            ])
+    };
+
+    send_reply(&ctx, CreateReply::default().embed(embed)).await;
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, status_provider, Context, Error},
+    status::{fetch_info_fields, fetch_queue_length, InfoField},
+    utils::send_reply,
+    BRAND_COLOR, BRAND_NAME_SHORT, ERROR_COLOR,
+};
+
+/// Every detail the server exposes about itself
+#[poise::command(slash_command)]
+pub async fn serverinfo(
+    ctx: Context<'_>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let status_provider = status_provider(&ctx).await;
+    let response = status_provider.get_players(&server.endpoint).await;
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} | Szerver Információk", server.name))
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now());
+
+    let embed = match &response {
+        Ok((players, server_info)) => {
+            let queue_length =
+                fetch_queue_length(status_provider.as_ref(), &server, &response).await;
+            let info_fields = fetch_info_fields(
+                status_provider.as_ref(),
+                &server.endpoint,
+                &InfoField::ALL,
+                &response,
+            )
+            .await;
+
+            embed
+                .field(
+                    "Elérhető Játékosok:",
+                    format!("{}/{}", players.len(), server_info.vars.max_players),
+                    true,
+                )
+                .fields(
+                    queue_length.map(|queue_length| ("Várólista:", queue_length.to_string(), true)),
+                )
+                .fields(
+                    info_fields
+                        .into_iter()
+                        .map(|(label, value)| (label, value, true)),
+                )
+                .color(BRAND_COLOR)
+        }
+        Err(_) => embed
+            .description("A szerver jelenleg nem elérhető!")
+            .color(ERROR_COLOR),
+    };
+
+    send_reply(&ctx, CreateReply::default().embed(embed).ephemeral(true)).await;
+
+    Ok(())
+}
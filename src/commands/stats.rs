@@ -0,0 +1,98 @@
+use chrono::TimeDelta;
+use poise::CreateReply;
+use serenity::all::{CreateAttachment, CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    chart::render_player_chart,
+    commands::{autocomplete_server, resolve_server, Context, Error},
+    history::PlayerHistory,
+    utils::send_reply,
+    BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+#[derive(poise::ChoiceParameter)]
+pub enum HistoryRange {
+    #[name = "24h"]
+    Day,
+    #[name = "7d"]
+    Week,
+}
+
+/// Server statistics
+#[poise::command(slash_command, subcommands("stats_players"))]
+pub async fn stats(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Player count over the last 24 hours or 7 days
+#[poise::command(slash_command, rename = "players")]
+pub async fn stats_players(
+    ctx: Context<'_>,
+    #[description = "The time range to show (default: 24h)"] range: Option<HistoryRange>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    let (since, range_name) = match range.unwrap_or(HistoryRange::Day) {
+        HistoryRange::Day => (TimeDelta::days(1), "utolsó 24 óra"),
+        HistoryRange::Week => (TimeDelta::days(7), "utolsó 7 nap"),
+    };
+
+    let samples = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let history = client_data.get::<PlayerHistory>().unwrap();
+        let samples = history.read().await.samples_since(&server.name, since);
+        samples
+    };
+
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        send_reply(
+            &ctx,
+            CreateReply::default()
+                .content("Még nincs elég adat a grafikonhoz!")
+                .ephemeral(true),
+        )
+        .await;
+        return Ok(());
+    };
+
+    let peak = samples
+        .iter()
+        .map(|sample| sample.players)
+        .max()
+        .unwrap_or(0);
+    let average =
+        samples.iter().map(|sample| sample.players).sum::<u32>() as f64 / samples.len() as f64;
+
+    let chart = render_player_chart(&samples)?;
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} | Játékosszám ({})", server.name, range_name))
+        .description(format!(
+            "<t:{}:f> - <t:{}:f>\n🟦 Játékosok 🟥 Maximum",
+            first.timestamp, last.timestamp
+        ))
+        .fields(vec![
+            ("Csúcs:", peak.to_string(), true),
+            ("Átlag:", format!("{:.1}", average), true),
+            ("Maximum:", last.max_players.to_string(), true),
+        ])
+        .image("attachment://players.png")
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    send_reply(
+        &ctx,
+        CreateReply::default()
+            .embed(embed)
+            .attachment(CreateAttachment::bytes(chart, "players.png")),
+    )
+    .await;
+
+    Ok(())
+}
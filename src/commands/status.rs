@@ -0,0 +1,74 @@
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, status_provider, Context, Error},
+    status::{
+        add_connect_field, add_server_status_fields, fetch_info_fields, fetch_queue_length,
+        next_restart_text,
+    },
+    utils::send_reply,
+    ClientData, BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+/// Current status of the server
+#[poise::command(slash_command)]
+pub async fn status(
+    ctx: Context<'_>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let (next_restart, status_info_fields) = {
+        let client_data = ctx.serenity_context().data.read().await;
+        let (_, config) = client_data.get::<ClientData>().unwrap();
+        let locked_config = config.read().await;
+
+        (
+            next_restart_text(&locked_config.restart_schedule),
+            locked_config.status_info_fields.clone(),
+        )
+    };
+
+    let status_provider = status_provider(&ctx).await;
+    let response = status_provider.get_players(&server.endpoint).await;
+    let queue_length = fetch_queue_length(status_provider.as_ref(), &server, &response).await;
+    let info_fields = fetch_info_fields(
+        status_provider.as_ref(),
+        &server.endpoint,
+        &status_info_fields,
+        &response,
+    )
+    .await;
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} | Szerver Státusz", server.name))
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    send_reply(
+        &ctx,
+        CreateReply::default()
+            .embed(add_connect_field(
+                add_server_status_fields(
+                    embed,
+                    &response,
+                    &next_restart,
+                    queue_length,
+                    &info_fields,
+                ),
+                &server,
+            ))
+            .ephemeral(true),
+    )
+    .await;
+
+    Ok(())
+}
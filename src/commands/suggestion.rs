@@ -0,0 +1,178 @@
+use poise::CreateReply;
+use serenity::all::{
+    ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, EditMessage, Timestamp, UserId,
+};
+use tracing::error;
+
+use crate::{
+    commands::{Context, Error},
+    suggestions::{reviewed_embed, SuggestionState, SuggestionStore},
+    utils::{edit_message, send_reply},
+    BRAND_NAME, BRAND_NAME_SHORT,
+};
+
+/// Review suggestions
+#[poise::command(
+    slash_command,
+    subcommands("accept", "deny", "consider", "implemented"),
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn suggestion(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Accept a suggestion
+#[poise::command(slash_command)]
+pub async fn accept(
+    ctx: Context<'_>,
+    #[description = "The number of the suggestion"] id: u16,
+    #[description = "Why it was accepted"]
+    #[max_length = 900]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    review(ctx, id, SuggestionState::Accepted, reason).await
+}
+
+/// Deny a suggestion
+#[poise::command(slash_command)]
+pub async fn deny(
+    ctx: Context<'_>,
+    #[description = "The number of the suggestion"] id: u16,
+    #[description = "Why it was denied"]
+    #[max_length = 900]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    review(ctx, id, SuggestionState::Denied, reason).await
+}
+
+/// Mark a suggestion as under consideration
+#[poise::command(slash_command)]
+pub async fn consider(
+    ctx: Context<'_>,
+    #[description = "The number of the suggestion"] id: u16,
+    #[description = "What is being considered"]
+    #[max_length = 900]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    review(ctx, id, SuggestionState::Considered, reason).await
+}
+
+/// Mark a suggestion as implemented
+#[poise::command(slash_command)]
+pub async fn implemented(
+    ctx: Context<'_>,
+    #[description = "The number of the suggestion"] id: u16,
+    #[description = "Where it was implemented, e.g. the update"]
+    #[max_length = 900]
+    reason: Option<String>,
+) -> Result<(), Error> {
+    review(ctx, id, SuggestionState::Implemented, reason).await
+}
+
+async fn review(
+    ctx: Context<'_>,
+    id: u16,
+    state: SuggestionState,
+    reason: Option<String>,
+) -> Result<(), Error> {
+    let client_data = ctx.serenity_context().data.read().await;
+    let store = client_data.get::<SuggestionStore>().unwrap().clone();
+    drop(client_data);
+
+    let Some(suggestion) = store.read().await.get(id).cloned() else {
+        reply_ephemeral(&ctx, "Nincs ilyen ötlet!").await;
+        return Ok(());
+    };
+
+    let channel_id = ChannelId::new(suggestion.channel_id);
+    let message = match channel_id.message(ctx.http(), suggestion.message_id).await {
+        Ok(message) => Some(message),
+        Err(e) => {
+            error!("Error fetching suggestion message: {e:?}");
+            None
+        }
+    };
+    let Some((message, embed)) = message.and_then(|message| {
+        let embed = message.embeds.first().cloned()?;
+        Some((message, embed))
+    }) else {
+        reply_ephemeral(&ctx, "Az ötlet üzenete nem található!").await;
+        return Ok(());
+    };
+
+    let reviewer_id = ctx.author().id;
+    let edited = edit_message(
+        ctx.http(),
+        channel_id,
+        suggestion.message_id,
+        EditMessage::new().embed(reviewed_embed(
+            embed,
+            state,
+            reviewer_id.get(),
+            reason.as_deref(),
+        )),
+    )
+    .await;
+
+    if edited.is_none() {
+        reply_ephemeral(&ctx, "Nem sikerült frissíteni az ötletet!").await;
+        return Ok(());
+    }
+
+    store
+        .write()
+        .await
+        .review(id, state, reviewer_id.get(), reason.clone());
+
+    let mut description = format!(
+        "A(z) **#{}** ötleted új státusza: **{}**",
+        id,
+        state.label()
+    );
+    if let Some(reason) = &reason {
+        description += &format!("\nIndok: {reason}");
+    }
+
+    let direct_message = CreateMessage::new().embed(
+        CreateEmbed::new()
+            .title(format!("{} | Ötlet - #{}", BRAND_NAME, id))
+            .description(description)
+            .url(message.link())
+            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+            .timestamp(Timestamp::now())
+            .color(state.color()),
+    );
+
+    // Users can disable direct messages, the review is kept anyway
+    let notified = match UserId::new(suggestion.author_id)
+        .direct_message(ctx.http(), direct_message)
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error!("Error sending direct message: {e:?}");
+            false
+        }
+    };
+
+    reply_ephemeral(
+        &ctx,
+        &format!(
+            "A(z) **#{}** ötlet státusza: **{}**{}",
+            id,
+            state.label(),
+            if notified {
+                ""
+            } else {
+                "\nA szerzőt nem sikerült értesíteni."
+            }
+        ),
+    )
+    .await;
+
+    Ok(())
+}
+
+async fn reply_ephemeral(ctx: &Context<'_>, content: &str) {
+    send_reply(ctx, CreateReply::default().content(content).ephemeral(true)).await;
+}
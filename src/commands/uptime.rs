@@ -0,0 +1,71 @@
+use chrono::{TimeDelta, Utc};
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, Context, Error},
+    uptime::UptimeLog,
+    utils::{format_duration, send_reply},
+    BRAND_COLOR, BRAND_NAME_SHORT,
+};
+
+/// Server uptime and availability
+#[poise::command(slash_command)]
+pub async fn uptime(
+    ctx: Context<'_>,
+    #[description = "The server to show (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    let client_data = ctx.serenity_context().data.read().await;
+    let uptime_log = client_data.get::<UptimeLog>().unwrap().read().await;
+
+    let now = Utc::now();
+
+    let current_state = match uptime_log.current_state(&server.name) {
+        Some((true, since)) => format!("✅ Elérhető ({})", format_duration(now - since)),
+        Some((false, since)) => format!("❌ Nem elérhető ({})", format_duration(now - since)),
+        None => String::from("Nincs adat"),
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} | Üzemidő", server.name))
+        .field("Jelenlegi Állapot:", current_state, false)
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    for (name, period) in [
+        ("24 óra", TimeDelta::days(1)),
+        ("7 nap", TimeDelta::days(7)),
+        ("30 nap", TimeDelta::days(30)),
+    ] {
+        let stats = uptime_log.stats(&server.name, period, now);
+
+        let value = match stats.availability {
+            Some(availability) => format!(
+                "Elérhetőség: **{:.2}%**\nLeállások: **{}**\nLeghosszabb leállás: **{}**",
+                availability * 100.0,
+                stats.outages,
+                stats
+                    .longest_outage
+                    .map(format_duration)
+                    .unwrap_or(String::from("-"))
+            ),
+            None => String::from("Nincs adat"),
+        };
+
+        embed = embed.field(format!("Utolsó {}:", name), value, true);
+    }
+
+    drop(uptime_log);
+    drop(client_data);
+
+    send_reply(&ctx, CreateReply::default().embed(embed)).await;
+
+    Ok(())
+}
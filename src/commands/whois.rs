@@ -0,0 +1,90 @@
+use poise::CreateReply;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, Timestamp};
+
+use crate::{
+    commands::{autocomplete_server, resolve_server, status_provider, Context, Error},
+    status::sanitize_name,
+    utils::send_reply,
+    BRAND_COLOR, BRAND_NAME_SHORT, ERROR_COLOR,
+};
+
+/// Maximum number of matching players shown, an embed can have at most 25 fields
+const MAX_RESULTS: usize = 10;
+
+/// Look up a player on the server by name or server id
+#[poise::command(slash_command)]
+pub async fn whois(
+    ctx: Context<'_>,
+    #[description = "Player name or server id"] player: String,
+    #[description = "The server to search (default: main server)"]
+    #[autocomplete = "autocomplete_server"]
+    server: Option<String>,
+) -> Result<(), Error> {
+    let Some(server) = resolve_server(&ctx, server.as_deref()).await else {
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("{} | Játékos Keresés", server.name))
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    match status_provider(&ctx)
+        .await
+        .get_players(&server.endpoint)
+        .await
+    {
+        Ok((players, _)) => {
+            let query = player.to_lowercase();
+            let server_id = player.trim_start_matches('#').parse::<u32>().ok();
+
+            let matches = players
+                .iter()
+                .filter(|player| match server_id {
+                    Some(server_id) => player.id == server_id,
+                    None => player.name.to_lowercase().contains(&query),
+                })
+                .collect::<Vec<_>>();
+
+            if matches.is_empty() {
+                embed = embed.description("Nincs találat.");
+            }
+
+            for player in matches.iter().take(MAX_RESULTS) {
+                let discord = match player.discord_id() {
+                    Some(discord_id) => format!("<@{}>", discord_id),
+                    None => String::from("Nincs összekapcsolva"),
+                };
+
+                embed = embed.field(
+                    sanitize_name(&player.name),
+                    format!(
+                        "**Szerver ID:** {}\n**Discord:** {}\n**Ping:** {}ms",
+                        player.id, discord, player.ping
+                    ),
+                    true,
+                );
+            }
+
+            if matches.len() > MAX_RESULTS {
+                embed = embed.description(format!(
+                    "{} találat, az első {} látható. Pontosítsd a keresést!",
+                    matches.len(),
+                    MAX_RESULTS
+                ));
+            }
+        }
+        Err(_) => {
+            embed = embed
+                .description("❌ A szerver nem elérhető!")
+                .color(ERROR_COLOR);
+        }
+    }
+
+    send_reply(&ctx, CreateReply::default().embed(embed).ephemeral(true)).await;
+
+    Ok(())
+}
@@ -1,19 +1,106 @@
-use std::{env, fs, io::Read};
+use std::{collections::HashMap, env, fs, io::Read, net::SocketAddr, str::FromStr, time::Duration};
 
+use chrono::{NaiveDate, NaiveTime};
+use chrono_tz::Tz;
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+use crate::cfx::ComponentFilter;
+use crate::report::{ReportPeriod, ReportSchedule};
+use crate::restart::RestartSchedule;
+use crate::status::InfoField;
+use crate::BRAND_NAME;
+use crate::BRAND_WEBSITE;
+
 pub struct Config {
     pub data_json: DataJson,
     pub token: String,
     pub suggestions_channel_id: u64,
     pub bug_report_channel_id: u64,
     pub bug_log_channel_id: u64,
-    pub status_channel_id: u64,
     pub cfx_status_channel_id: u64,
     pub guild_id: u64,
     pub help_channel_id: u64,
-    pub fivem_ip: String,
+    /// The first server is the main one, restart warnings are posted to its status channel
+    pub fivem_servers: Vec<FivemServer>,
+    pub restart_schedule: RestartSchedule,
+    pub alerts_channel_id: Option<u64>,
+    pub staff_role_id: Option<u64>,
+    pub downtime_failure_threshold: u32,
+    pub activity_log_channel_id: Option<u64>,
+    pub counter_channels: Vec<CounterChannel>,
+    pub status_interval: Duration,
+    pub cfx_status_interval: Duration,
+    /// Timeout of the requests made to the FiveM servers and the CFX status page
+    pub http_timeout: Duration,
+    /// The polling delay doubles on repeated failures, up to this
+    pub max_backoff: Duration,
+    /// Server details shown in the status messages
+    pub status_info_fields: Vec<InfoField>,
+    pub cfx_component_filter: ComponentFilter,
+    pub report_channel_id: Option<u64>,
+    pub report_schedule: ReportSchedule,
+    /// Link buttons below every status message, after the connect button of the server
+    pub status_buttons: Vec<LinkButton>,
+    /// The webhook receiver is only started if this is set
+    pub webhook: Option<WebhookConfig>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct FivemServer {
+    pub name: String,
+    pub endpoint: String,
+    #[serde(rename = "statusChannelId")]
+    pub status_channel_id: u64,
+    /// Resources that should always be running, staff is alerted when one is missing
+    #[serde(rename = "requiredResources", default)]
+    pub required_resources: Vec<String>,
+    /// JSON endpoint exposing the length of the connection queue, e.g. a queue resource's export
+    #[serde(rename = "queueEndpoint")]
+    pub queue_endpoint: Option<String>,
+    /// JSON pointer (e.g. `/queue/count`) or top level key of the queue length, `queue` if not set
+    #[serde(rename = "queueField")]
+    pub queue_field: Option<String>,
+    /// The cfx.re join link of the server, e.g. `https://cfx.re/join/abc123`
+    #[serde(rename = "connectUrl")]
+    pub connect_url: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct LinkButton {
+    pub label: String,
+    pub url: String,
+}
+
+/// A channel, usually a locked voice channel, whose name shows the status of a server
+#[derive(Deserialize, Clone)]
+pub struct CounterChannel {
+    #[serde(rename = "channelId")]
+    pub channel_id: u64,
+    /// The channel name with `{players}`, `{max_players}`, `{queue}`, `{status}` and `{emoji}` placeholders
+    pub template: String,
+    /// The name of the server to show, the main server if not set
+    pub server: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct WebhookConfig {
+    pub address: SocketAddr,
+    /// Expected as a bearer token in the `Authorization` header
+    pub token: String,
+    pub channel_id: u64,
+    /// Channels of specific event types, e.g. `playerBanned`, the others are sent to `channel_id`
+    pub event_channels: HashMap<String, u64>,
+}
+
+impl WebhookConfig {
+    pub fn channel_id(&self, event_name: &str) -> u64 {
+        self.event_channels
+            .get(event_name)
+            .copied()
+            .unwrap_or(self.channel_id)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -22,10 +109,24 @@ pub struct DataJson {
     pub suggestion_count: u16,
     #[serde(rename = "bugReportCount")]
     pub bug_report_count: u16,
-    #[serde(rename = "statusMessageId")]
-    pub status_message_id: Option<u64>,
+    /// Only read to migrate from the time a single server was supported
+    #[serde(rename = "statusMessageId", skip_serializing)]
+    pub legacy_status_message_id: Option<u64>,
+    /// Status message ids by server name
+    #[serde(rename = "statusMessageIds", default)]
+    pub status_message_ids: HashMap<String, u64>,
     #[serde(rename = "cfxStatusMessageId")]
     pub cfx_status_message_id: Option<u64>,
+    /// Names of the announced, not yet resolved CFX incidents by id, `None` until the first CFX status
+    #[serde(rename = "cfxIncidents", default)]
+    pub cfx_incidents: Option<HashMap<String, String>>,
+    /// The local date the activity reports were last posted on
+    #[serde(rename = "lastReportDate", default)]
+    pub last_report_date: Option<NaiveDate>,
+    #[serde(rename = "musicPanelChannelId")]
+    pub music_panel_channel_id: Option<u64>,
+    #[serde(rename = "musicPanelMessageId")]
+    pub music_panel_message_id: Option<u64>,
 }
 
 impl Default for DataJson {
@@ -39,8 +140,13 @@ impl DataJson {
         Self {
             suggestion_count: 0,
             bug_report_count: 0,
-            status_message_id: None,
+            legacy_status_message_id: None,
+            status_message_ids: HashMap::new(),
             cfx_status_message_id: None,
+            cfx_incidents: None,
+            last_report_date: None,
+            music_panel_channel_id: None,
+            music_panel_message_id: None,
         }
     }
 
@@ -78,21 +184,40 @@ impl DataJson {
         .expect("Expected to write to `data.json` file");
     }
 
-    pub fn increment_suggestion_count(&mut self) {
+    /// Returns the number of the new suggestion
+    pub fn increment_suggestion_count(&mut self) -> u16 {
         self.suggestion_count += 1;
+        self.suggestion_count
     }
 
     pub fn increment_bug_report_count(&mut self) {
         self.bug_report_count += 1;
     }
 
-    pub fn set_status_message_id(&mut self, id: u64) {
-        self.status_message_id = Some(id);
+    pub fn set_status_message_id(&mut self, server_name: &str, id: u64) {
+        self.status_message_ids.insert(server_name.to_string(), id);
+    }
+
+    pub fn clear_status_message_id(&mut self, server_name: &str) {
+        self.status_message_ids.remove(server_name);
     }
 
     pub fn set_cfx_status_message_id(&mut self, id: u64) {
         self.cfx_status_message_id = Some(id);
     }
+
+    pub fn clear_cfx_status_message_id(&mut self) {
+        self.cfx_status_message_id = None;
+    }
+
+    pub fn set_last_report_date(&mut self, date: NaiveDate) {
+        self.last_report_date = Some(date);
+    }
+
+    pub fn set_music_panel(&mut self, channel_id: u64, message_id: u64) {
+        self.music_panel_channel_id = Some(channel_id);
+        self.music_panel_message_id = Some(message_id);
+    }
 }
 
 impl Default for Config {
@@ -116,10 +241,6 @@ impl Config {
             .expect("Expected `BUG_LOG_CHANNEL_ID` in the environment")
             .parse::<u64>()
             .expect("Expected `BUG_LOG_CHANNEL_ID` to be a number");
-        let status_channel_id = env::var("STATUS_CHANNEL_ID")
-            .expect("Expected `STATUS_CHANNEL_ID` in the environment")
-            .parse::<u64>()
-            .expect("Expected `STATUS_CHANNEL_ID` to be a number");
         let guild_id = env::var("GUILD_ID")
             .expect("Expected `GUILD_ID` in the environment")
             .parse::<u64>()
@@ -132,19 +253,254 @@ impl Config {
             .expect("Expected `CFX_STATUS_CHANNEL_ID` in the environment")
             .parse::<u64>()
             .expect("Expected `CFX_STATUS_CHANNEL_ID` to be a number");
-        let fivem_ip = env::var("FIVEM_IP").expect("Expected `FIVEM_IP` in the environment");
+        let fivem_servers: Vec<FivemServer> = match env::var("FIVEM_SERVERS") {
+            Ok(servers) => serde_json::from_str(&servers)
+                .expect("Expected `FIVEM_SERVERS` to be a JSON list of servers"),
+            Err(_) => vec![FivemServer {
+                name: BRAND_NAME.to_string(),
+                endpoint: env::var("FIVEM_IP")
+                    .expect("Expected `FIVEM_IP` or `FIVEM_SERVERS` in the environment"),
+                status_channel_id: env::var("STATUS_CHANNEL_ID")
+                    .expect("Expected `STATUS_CHANNEL_ID` in the environment")
+                    .parse::<u64>()
+                    .expect("Expected `STATUS_CHANNEL_ID` to be a number"),
+                required_resources: comma_separated_env_var("REQUIRED_RESOURCES", ""),
+                queue_endpoint: optional_env_var("QUEUE_ENDPOINT"),
+                queue_field: optional_env_var("QUEUE_FIELD"),
+                connect_url: optional_env_var("CONNECT_URL"),
+            }],
+        };
+        assert!(
+            !fivem_servers.is_empty(),
+            "Expected at least one server in `FIVEM_SERVERS`"
+        );
+        // Jobs and stored data are keyed by the server name
+        for (i, server) in fivem_servers.iter().enumerate() {
+            assert!(
+                !fivem_servers[..i]
+                    .iter()
+                    .any(|other| other.name == server.name),
+                "Expected the server names in `FIVEM_SERVERS` to be unique, found `{}` twice",
+                server.name
+            );
+        }
+        let restart_times =
+            RestartSchedule::parse_times(&env::var("RESTART_TIMES").unwrap_or_default());
+        let restart_timezone = env::var("RESTART_TIMEZONE")
+            .unwrap_or(String::from("Europe/Budapest"))
+            .parse::<Tz>()
+            .expect("Expected `RESTART_TIMEZONE` to be a valid IANA timezone");
+        let restart_warning_minutes = comma_separated_env_var("RESTART_WARNING_MINUTES", "30,10,5")
+            .iter()
+            .map(|minutes| {
+                minutes
+                    .parse::<i64>()
+                    .expect("Expected `RESTART_WARNING_MINUTES` to be a list of numbers")
+            })
+            .collect();
+        let alerts_channel_id = optional_env_var("ALERTS_CHANNEL_ID");
+        let staff_role_id = optional_env_var("STAFF_ROLE_ID");
+        let downtime_failure_threshold =
+            optional_env_var("DOWNTIME_FAILURE_THRESHOLD").unwrap_or(3);
+        let activity_log_channel_id = optional_env_var("ACTIVITY_LOG_CHANNEL_ID");
+        let counter_channels: Vec<CounterChannel> = match env::var("COUNTER_CHANNELS") {
+            Ok(channels) if !channels.is_empty() => serde_json::from_str(&channels)
+                .expect("Expected `COUNTER_CHANNELS` to be a JSON list of counter channels"),
+            _ => Vec::new(),
+        };
+        for server_name in counter_channels
+            .iter()
+            .filter_map(|channel| channel.server.as_ref())
+        {
+            assert!(
+                fivem_servers.iter().any(|server| &server.name == server_name),
+                "Expected the servers in `COUNTER_CHANNELS` to be configured, found `{server_name}`"
+            );
+        }
+        let status_interval =
+            Duration::from_secs(optional_env_var("STATUS_INTERVAL_SECONDS").unwrap_or(10));
+        let cfx_status_interval =
+            Duration::from_secs(optional_env_var("CFX_STATUS_INTERVAL_SECONDS").unwrap_or(10));
+        let http_timeout =
+            Duration::from_secs(optional_env_var("HTTP_TIMEOUT_SECONDS").unwrap_or(5));
+        let max_backoff =
+            Duration::from_secs(optional_env_var("MAX_BACKOFF_SECONDS").unwrap_or(300));
+        let status_info_fields = comma_separated_env_var("STATUS_INFO_FIELDS", "")
+            .iter()
+            .map(|field| {
+                field.parse::<InfoField>().unwrap_or_else(|_| {
+                    panic!("Expected `STATUS_INFO_FIELDS` to only contain known fields, found `{field}`")
+                })
+            })
+            .collect();
+        let cfx_component_filter = ComponentFilter {
+            include: comma_separated_env_var("CFX_COMPONENTS_INCLUDE", ""),
+            exclude: comma_separated_env_var(
+                "CFX_COMPONENTS_EXCLUDE",
+                "Server List Frontend,RedM,\"Runtime\",IDMS",
+            ),
+        };
+        let report_channel_id = optional_env_var("REPORT_CHANNEL_ID");
+        let report_periods = comma_separated_env_var("REPORT_PERIODS", "weekly")
+            .iter()
+            .map(|period| {
+                period.parse::<ReportPeriod>().unwrap_or_else(|_| {
+                    panic!("Expected `REPORT_PERIODS` to only contain `daily` and `weekly`, found `{period}`")
+                })
+            })
+            .collect();
+        let report_time = NaiveTime::parse_from_str(
+            &env::var("REPORT_TIME").unwrap_or(String::from("08:00")),
+            "%H:%M",
+        )
+        .expect("Expected `REPORT_TIME` to be a time in `HH:MM` format");
+        let status_buttons: Vec<LinkButton> = match env::var("STATUS_BUTTONS") {
+            Ok(buttons) if !buttons.is_empty() => serde_json::from_str(&buttons)
+                .expect("Expected `STATUS_BUTTONS` to be a JSON list of link buttons"),
+            _ => vec![
+                LinkButton {
+                    label: String::from("Weboldal"),
+                    url: BRAND_WEBSITE.to_string(),
+                },
+                LinkButton {
+                    label: String::from("Segítségkérés"),
+                    url: format!("https://discord.com/channels/{guild_id}/{help_channel_id}"),
+                },
+            ],
+        };
+        let webhook = optional_env_var::<SocketAddr>("WEBHOOK_ADDRESS").map(|address| WebhookConfig {
+            address,
+            token: optional_env_var("WEBHOOK_TOKEN")
+                .expect("Expected `WEBHOOK_TOKEN` in the environment when `WEBHOOK_ADDRESS` is set"),
+            channel_id: optional_env_var("WEBHOOK_CHANNEL_ID").expect(
+                "Expected `WEBHOOK_CHANNEL_ID` in the environment when `WEBHOOK_ADDRESS` is set",
+            ),
+            event_channels: match env::var("WEBHOOK_EVENT_CHANNELS") {
+                Ok(channels) if !channels.is_empty() => serde_json::from_str(&channels)
+                    .expect("Expected `WEBHOOK_EVENT_CHANNELS` to be a JSON object of channel ids"),
+                _ => HashMap::new(),
+            },
+        });
+
+        for button in &status_buttons {
+            check_link_url("every URL in `STATUS_BUTTONS`", &button.url);
+        }
+        for server in &fivem_servers {
+            if let Some(url) = &server.connect_url {
+                check_link_url(&format!("the connect URL of `{}`", server.name), url);
+            }
+        }
+
+        let mut data_json = DataJson::new().load();
+        if let Some(id) = data_json.legacy_status_message_id.take() {
+            data_json
+                .status_message_ids
+                .entry(fivem_servers[0].name.clone())
+                .or_insert(id);
+            data_json.save();
+        }
 
         Self {
-            data_json: DataJson::new().load(),
+            data_json,
             token,
             suggestions_channel_id,
             bug_report_channel_id,
             bug_log_channel_id,
-            status_channel_id,
             cfx_status_channel_id,
             guild_id,
             help_channel_id,
-            fivem_ip,
+            fivem_servers,
+            restart_schedule: RestartSchedule {
+                times: restart_times,
+                timezone: restart_timezone,
+                warning_minutes: restart_warning_minutes,
+            },
+            alerts_channel_id,
+            staff_role_id,
+            downtime_failure_threshold,
+            activity_log_channel_id,
+            counter_channels,
+            status_interval,
+            cfx_status_interval,
+            http_timeout,
+            max_backoff,
+            status_info_fields,
+            cfx_component_filter,
+            report_channel_id,
+            report_schedule: ReportSchedule {
+                periods: report_periods,
+                time: report_time,
+                timezone: restart_timezone,
+            },
+            status_buttons,
+            webhook,
         }
     }
+
+    pub fn main_server(&self) -> &FivemServer {
+        &self.fivem_servers[0]
+    }
+
+    pub fn server(&self, name: &str) -> Option<&FivemServer> {
+        self.fivem_servers.iter().find(|server| server.name == name)
+    }
+
+    /// The buttons below the status message of the given server, its connect button first
+    pub fn status_buttons(&self, server: &FivemServer) -> Vec<LinkButton> {
+        server
+            .connect_url
+            .iter()
+            .map(|url| LinkButton {
+                label: String::from("Csatlakozás"),
+                url: url.clone(),
+            })
+            .chain(self.status_buttons.iter().cloned())
+            .collect()
+    }
+
+    /// The counter channels showing the given server
+    pub fn counter_channels(&self, server_name: &str) -> Vec<CounterChannel> {
+        self.counter_channels
+            .iter()
+            .filter(|channel| {
+                channel
+                    .server
+                    .as_deref()
+                    .unwrap_or(&self.main_server().name)
+                    == server_name
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Reads an optional environment variable, panicking if it is set but can't be parsed
+fn optional_env_var<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name)
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .parse::<T>()
+                .unwrap_or_else(|_| panic!("Expected `{name}` to be a valid value"))
+        })
+}
+
+/// Reads a comma separated list from the environment, an empty variable means an empty list
+fn comma_separated_env_var(name: &str, default: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or(String::from(default))
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Panics unless the URL can be used in a link button, Discord rejects the whole message otherwise
+fn check_link_url(what: &str, url: &str) {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => panic!("Expected {what} to be an http(s) URL, found `{url}`"),
+    }
 }
@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+
+use crate::config::CounterChannel;
+use crate::status::truncate;
+
+/// Discord allows renaming a channel twice every 10 minutes
+const RENAME_LIMIT: usize = 2;
+const RENAME_WINDOW: TimeDelta = TimeDelta::minutes(10);
+/// Discord's limit on the length of a channel name
+const MAX_CHANNEL_NAME_LENGTH: usize = 100;
+
+pub struct CounterValues<'a> {
+    pub online: bool,
+    pub players: usize,
+    pub max_players: &'a str,
+    /// `None` if the server has no queue endpoint or it couldn't be read
+    pub queue_length: Option<u32>,
+}
+
+/// Fills in the `{players}`, `{max_players}`, `{queue}`, `{status}` and `{emoji}` placeholders of the template
+pub fn render_counter(template: &str, values: &CounterValues) -> String {
+    let (status, emoji) = match values.online {
+        true => ("Elérhető", "🟢"),
+        false => ("Nem elérhető", "🔴"),
+    };
+
+    let name = template
+        .replace("{players}", &values.players.to_string())
+        .replace("{max_players}", values.max_players)
+        .replace(
+            "{queue}",
+            &values
+                .queue_length
+                .map(|queue_length| queue_length.to_string())
+                .unwrap_or(String::from("-")),
+        )
+        .replace("{status}", status)
+        .replace("{emoji}", emoji);
+
+    truncate(&name, MAX_CHANNEL_NAME_LENGTH).to_string()
+}
+
+/// Keeps track of a counter channel's name to rename it only on change and within the rate limit
+pub struct CounterChannelState {
+    pub channel: CounterChannel,
+    name: Option<String>,
+    renames: VecDeque<DateTime<Utc>>,
+}
+
+impl CounterChannelState {
+    pub fn new(channel: CounterChannel) -> Self {
+        Self {
+            channel,
+            name: None,
+            renames: VecDeque::new(),
+        }
+    }
+
+    /// Whether the channel should be renamed to `name` now
+    pub fn should_rename(&mut self, name: &str, now: DateTime<Utc>) -> bool {
+        if self.name.as_deref() == Some(name) {
+            return false;
+        }
+
+        while self
+            .renames
+            .front()
+            .is_some_and(|renamed_at| now - *renamed_at >= RENAME_WINDOW)
+        {
+            self.renames.pop_front();
+        }
+
+        self.renames.len() < RENAME_LIMIT
+    }
+
+    /// Records a successful rename, failed ones are retried on the next tick
+    pub fn record_rename(&mut self, name: &str, now: DateTime<Utc>) {
+        self.renames.push_back(now);
+        self.name = Some(name.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> CounterChannelState {
+        CounterChannelState::new(CounterChannel {
+            channel_id: 1,
+            template: String::from("{players}"),
+            server: None,
+        })
+    }
+
+    #[test]
+    fn renames_on_change_within_the_rate_limit() {
+        let mut state = state();
+        let now = Utc::now();
+
+        assert!(state.should_rename("1", now));
+        state.record_rename("1", now);
+        assert!(!state.should_rename("1", now));
+
+        assert!(state.should_rename("2", now));
+        state.record_rename("2", now);
+        assert!(!state.should_rename("3", now + TimeDelta::minutes(9)));
+        assert!(state.should_rename("3", now + RENAME_WINDOW));
+    }
+
+    #[test]
+    fn retries_failed_renames() {
+        let mut state = state();
+        let now = Utc::now();
+
+        // Nothing is recorded when the rename fails
+        assert!(state.should_rename("1", now));
+        assert!(state.should_rename("1", now));
+        assert!(state.should_rename("1", now));
+    }
+}
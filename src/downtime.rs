@@ -0,0 +1,119 @@
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+
+pub enum Transition {
+    WentOffline { since: DateTime<Utc> },
+    CameOnline { outage: TimeDelta },
+}
+
+/// Tracks consecutive `get_players` failures to detect when the server goes offline or recovers
+pub struct DowntimeTracker {
+    failure_threshold: u32,
+    consecutive_failures: u32,
+    first_failure_at: Option<DateTime<Utc>>,
+    offline: bool,
+}
+
+impl DowntimeTracker {
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            failure_threshold,
+            consecutive_failures: 0,
+            first_failure_at: None,
+            offline: false,
+        }
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn record_success(&mut self) -> Option<Transition> {
+        let first_failure_at = self.first_failure_at.take();
+        self.consecutive_failures = 0;
+
+        if !self.offline {
+            return None;
+        }
+
+        self.offline = false;
+
+        first_failure_at.map(|since| Transition::CameOnline {
+            outage: Utc::now() - since,
+        })
+    }
+
+    pub fn record_failure(&mut self) -> Option<Transition> {
+        let since = *self.first_failure_at.get_or_insert_with(Utc::now);
+        self.consecutive_failures += 1;
+
+        if self.offline || self.consecutive_failures < self.failure_threshold {
+            return None;
+        }
+
+        self.offline = true;
+
+        Some(Transition::WentOffline { since })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn goes_offline_at_the_failure_threshold() {
+        let mut tracker = DowntimeTracker::new(3);
+
+        assert!(tracker.record_failure().is_none());
+        assert!(tracker.record_failure().is_none());
+        assert!(!tracker.is_offline());
+
+        assert!(matches!(
+            tracker.record_failure(),
+            Some(Transition::WentOffline { .. })
+        ));
+        assert!(tracker.is_offline());
+
+        // Only the transition is reported
+        assert!(tracker.record_failure().is_none());
+        assert!(tracker.is_offline());
+
+        assert!(matches!(
+            tracker.record_success(),
+            Some(Transition::CameOnline { .. })
+        ));
+        assert!(!tracker.is_offline());
+        assert!(tracker.record_success().is_none());
+    }
+
+    #[test]
+    fn resets_the_failures_on_success() {
+        let mut tracker = DowntimeTracker::new(2);
+
+        assert!(tracker.record_failure().is_none());
+        assert!(tracker.record_success().is_none());
+        assert!(tracker.record_failure().is_none());
+        assert!(!tracker.is_offline());
+    }
+
+    #[test]
+    fn measures_the_outage_from_the_first_failure() {
+        let mut tracker = DowntimeTracker::new(2);
+
+        tracker.record_failure();
+        let first_failure_at = tracker.first_failure_at.unwrap();
+
+        let Some(Transition::WentOffline { since }) = tracker.record_failure() else {
+            panic!("Expected the server to go offline");
+        };
+        assert_eq!(since, first_failure_at);
+
+        let Some(Transition::CameOnline { outage }) = tracker.record_success() else {
+            panic!("Expected the server to come online");
+        };
+        assert!(outage >= TimeDelta::zero());
+        assert!(tracker.first_failure_at.is_none());
+    }
+}
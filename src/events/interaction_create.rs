@@ -0,0 +1,126 @@
+use rand::seq::SliceRandom;
+use serenity::all::ComponentInteraction;
+use serenity::all::Context;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::EventHandler;
+use serenity::all::Interaction;
+use serenity::async_trait;
+use songbird::tracks::LoopState;
+use songbird::tracks::PlayMode;
+use tracing::error;
+
+use crate::music::create_music_panel;
+use crate::music::MAX_VOLUME;
+use crate::music::MUSIC_LOOP;
+use crate::music::MUSIC_PLAY_PAUSE;
+use crate::music::MUSIC_SHUFFLE;
+use crate::music::MUSIC_SKIP;
+use crate::music::MUSIC_STOP;
+use crate::music::MUSIC_VOLUME_DOWN;
+use crate::music::MUSIC_VOLUME_UP;
+use crate::music::VOLUME_STEP;
+use crate::utils::respond_to_interaction;
+
+pub struct InteractionHandler;
+
+#[async_trait]
+impl EventHandler for InteractionHandler {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        // Slash commands are handled by poise, only buttons are handled here
+        let Interaction::Component(component) = interaction else {
+            return;
+        };
+
+        if component.data.custom_id.starts_with("music_") {
+            music_panel_button(&ctx, &component).await;
+        }
+    }
+}
+
+async fn music_panel_button(ctx: &Context, component: &ComponentInteraction) {
+    let Some(guild_id) = component.guild_id else {
+        error!("Received music panel interaction without guild");
+        return;
+    };
+
+    let manager = songbird::get(ctx)
+        .await
+        .expect("Expected songbird voice client to be placed in at initialization");
+
+    let Some(handler_lock) = manager.get(guild_id) else {
+        respond_to_interaction(
+            &ctx.http,
+            component,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("Not in a voice channel")
+                    .ephemeral(true),
+            ),
+        )
+        .await;
+        return;
+    };
+
+    {
+        let handler = handler_lock.lock().await;
+        let queue = handler.queue();
+        let current = queue.current();
+
+        let result = match (component.data.custom_id.as_str(), current) {
+            (MUSIC_SKIP, _) => queue.skip(),
+            (MUSIC_STOP, _) => {
+                queue.stop();
+                Ok(())
+            }
+            (MUSIC_SHUFFLE, _) => {
+                queue.modify_queue(|q| {
+                    // The first element is the track that is currently playing
+                    if q.len() > 2 {
+                        q.make_contiguous()[1..].shuffle(&mut rand::thread_rng());
+                    }
+                });
+                Ok(())
+            }
+            (MUSIC_PLAY_PAUSE, Some(track)) => match track.get_info().await {
+                Ok(info) if info.playing == PlayMode::Play => track.pause(),
+                Ok(_) => track.play(),
+                Err(e) => Err(e),
+            },
+            (MUSIC_LOOP, Some(track)) => match track.get_info().await {
+                Ok(info) if info.loops == LoopState::Infinite => track.disable_loop(),
+                Ok(_) => track.enable_loop(),
+                Err(e) => Err(e),
+            },
+            (MUSIC_VOLUME_DOWN, Some(track)) => match track.get_info().await {
+                Ok(info) => track.set_volume((info.volume - VOLUME_STEP).max(0.0)),
+                Err(e) => Err(e),
+            },
+            (MUSIC_VOLUME_UP, Some(track)) => match track.get_info().await {
+                Ok(info) => track.set_volume((info.volume + VOLUME_STEP).min(MAX_VOLUME)),
+                Err(e) => Err(e),
+            },
+            _ => Ok(()),
+        };
+
+        if let Err(e) = result {
+            error!(
+                "Failed to handle music panel button `{}`: {e:?}",
+                component.data.custom_id
+            );
+        }
+    }
+
+    let (embed, components) = create_music_panel(Some(handler_lock)).await;
+
+    respond_to_interaction(
+        &ctx.http,
+        component,
+        CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .components(components),
+        ),
+    )
+    .await;
+}
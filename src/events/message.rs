@@ -1,3 +1,4 @@
+use chrono::Utc;
 use serenity::all::ChannelId;
 use tracing::error;
 
@@ -12,7 +13,10 @@ use serenity::all::Timestamp;
 use serenity::async_trait;
 use tracing::info;
 
+use crate::submissions::SubmissionLog;
+use crate::suggestions::SuggestionStore;
 use crate::utils::delete_message;
+use crate::utils::error_reply;
 use crate::utils::react_to_message;
 use crate::utils::send_message;
 use crate::ClientData;
@@ -20,6 +24,9 @@ use crate::BRAND_COLOR;
 use crate::BRAND_ICON;
 use crate::WARNING_COLOR;
 
+/// Shown when a bug report can't be forwarded to the bug log channel
+const BUG_REPORT_ERROR: &str = "Hibajelentésed nem sikerült elküldeni, kérlek próbáld újra később!";
+
 pub struct MessageHandler;
 
 #[async_trait]
@@ -46,7 +53,14 @@ async fn suggestion(ctx: &Context, msg: &Message) {
     let client_data = ctx.data.read().await;
     let (_, config) = client_data.get::<ClientData>().unwrap();
 
-    config.write().await.data_json.increment_suggestion_count();
+    // Read under the same lock as the increment, so close suggestions get their own numbers
+    let number = config.write().await.data_json.increment_suggestion_count();
+    client_data
+        .get::<SubmissionLog>()
+        .unwrap()
+        .write()
+        .await
+        .record_suggestion(Utc::now());
 
     let embed = CreateMessage::new().embed(
         CreateEmbed::new()
@@ -54,10 +68,7 @@ async fn suggestion(ctx: &Context, msg: &Message) {
                 CreateEmbedAuthor::new(&msg.author.name)
                     .icon_url(msg.author.avatar_url().unwrap_or(BRAND_ICON.to_string())),
             )
-            .title(format!(
-                "Ötlet - #{}",
-                config.read().await.data_json.suggestion_count
-            ))
+            .title(format!("Ötlet - #{}", number))
             .description(&msg.content)
             .timestamp(Timestamp::now())
             .color(BRAND_COLOR),
@@ -85,6 +96,18 @@ async fn suggestion(ctx: &Context, msg: &Message) {
             // Save suggestion_count to json file
             config.read().await.data_json.save();
 
+            client_data
+                .get::<SuggestionStore>()
+                .unwrap()
+                .write()
+                .await
+                .add(
+                    number,
+                    msg.author.id.get(),
+                    msg.channel_id.get(),
+                    suggestion_msg.id.get(),
+                );
+
             info!(
                 "Suggestion message received with id: {}",
                 msg.id.to_string()
@@ -101,6 +124,31 @@ async fn bug_report(ctx: &Context, msg: &Message) {
     let (_, config) = client_data.get::<ClientData>().unwrap();
 
     config.write().await.data_json.increment_bug_report_count();
+    client_data
+        .get::<SubmissionLog>()
+        .unwrap()
+        .write()
+        .await
+        .record_bug_report(Utc::now());
+
+    let log_channel = match ctx
+        .http
+        .get_channel(ChannelId::new(config.read().await.bug_log_channel_id))
+        .await
+        .map(|channel| channel.guild())
+    {
+        Ok(Some(log_channel)) => log_channel,
+        Ok(None) => {
+            error!("The bug log channel is not in a guild");
+            send_message(&ctx.http, msg.channel_id, error_reply(BUG_REPORT_ERROR)).await;
+            return;
+        }
+        Err(e) => {
+            error!("Error getting the bug log channel: {e:?}");
+            send_message(&ctx.http, msg.channel_id, error_reply(BUG_REPORT_ERROR)).await;
+            return;
+        }
+    };
 
     let user_embed = CreateMessage::new().embed(
         CreateEmbed::new()
@@ -121,14 +169,6 @@ async fn bug_report(ctx: &Context, msg: &Message) {
 
     send_message(&ctx.http, msg.channel_id, user_embed).await;
 
-    let log_channel = ctx
-        .http
-        .get_channel(ChannelId::new(config.read().await.bug_log_channel_id))
-        .await
-        .expect("Expected to find the bug log channel")
-        .guild()
-        .expect("Expected the bug log channel to be in a guild");
-
     let log_embed = CreateMessage::new().embed(
         CreateEmbed::new()
             .author(
@@ -1,3 +1,4 @@
+pub mod interaction_create;
 pub mod message;
 pub mod ready;
 pub mod voice_state_update;
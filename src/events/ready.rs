@@ -1,388 +1,1116 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
-use serde::Deserialize;
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serenity::all::ActivityData;
 use serenity::all::ChannelId;
-use serenity::all::CreateActionRow;
-use serenity::all::CreateButton;
+use serenity::all::CreateAllowedMentions;
 use serenity::all::CreateEmbed;
 use serenity::all::CreateEmbedFooter;
 use serenity::all::CreateMessage;
+use serenity::all::EditChannel;
 use serenity::all::EditMessage;
+use serenity::all::OnlineStatus;
 use serenity::all::Ready;
+use serenity::all::RoleId;
 use serenity::all::Timestamp;
 use serenity::async_trait;
 use serenity::prelude::*;
-use tokio::task;
 use tokio::time;
 use tracing::error;
 use tracing::info;
 
-use crate::utils::edit_message;
+use crate::activity::ActivityChanges;
+use crate::activity::ActivityTracker;
+use crate::activity::Session;
+use crate::backoff::Backoff;
+use crate::cfx::add_cfx_status_fields;
+use crate::cfx::diff_incidents;
+use crate::cfx::new_incident_embed;
+use crate::cfx::resolved_incident_embed;
+use crate::cfx::CFXStatus;
+use crate::config::Config;
+use crate::config::FivemServer;
+use crate::counters::render_counter;
+use crate::counters::CounterChannelState;
+use crate::counters::CounterValues;
+use crate::downtime::DowntimeTracker;
+use crate::downtime::Transition;
+use crate::fivem::Player;
+use crate::fivem::ServerInfo;
+use crate::history::PlayerHistory;
+use crate::playtime::PlaytimeStore;
+use crate::provider::StatusProvider;
+use crate::provider::StatusProviderKey;
+use crate::report::ActivityReport;
+use crate::resources::ResourceChanges;
+use crate::resources::ResourceMonitor;
+use crate::status::add_connect_field;
+use crate::status::add_server_status_fields;
+use crate::status::fetch_info_fields;
+use crate::status::fetch_queue_length;
+use crate::status::link_button_rows;
+use crate::status::next_restart_text;
+use crate::status::sanitize_name;
+use crate::status::truncate;
+use crate::status::MAX_DESCRIPTION_LENGTH;
+use crate::status::MAX_FIELD_LENGTH;
+use crate::submissions::SubmissionLog;
+use crate::supervisor::Supervisor;
+use crate::uptime::UptimeLog;
+use crate::utils::edit_tracked_message;
+use crate::utils::format_duration;
 use crate::utils::send_message;
+use crate::utils::EditError;
+use crate::webhook::serve_webhooks;
 use crate::ClientData;
 use crate::BRAND_COLOR;
 use crate::BRAND_NAME;
 use crate::BRAND_NAME_SHORT;
-use crate::BRAND_WEBSITE;
 use crate::ERROR_COLOR;
 use crate::SUCCESS_COLOR;
+use crate::WARNING_COLOR;
+
+/// Discord's limit on the number of embeds in a message
+const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+/// Discord's limit on the combined length of the embeds in a message
+const MAX_MESSAGE_EMBEDS_LENGTH: usize = 6000;
 
 pub struct ReadyHandler;
 
+/// The last content of a status message, to only edit it when something changed
+struct StatusMessageState {
+    /// The embed without its footer and timestamp
+    last_embed: Option<CreateEmbed>,
+    /// Alternates on every edit, so it's visible that the message is being updated
+    footer_emoji: &'static str,
+}
+
+impl Default for StatusMessageState {
+    fn default() -> Self {
+        Self {
+            last_embed: None,
+            footer_emoji: "⚫",
+        }
+    }
+}
+
+impl StatusMessageState {
+    /// Whether the message already shows this embed
+    fn is_unchanged(&self, embed: &CreateEmbed) -> bool {
+        self.last_embed.as_ref() == Some(embed)
+    }
+
+    /// Adds the footer and the timestamp to the embed before it is sent
+    fn finish_embed(&mut self, embed: CreateEmbed) -> CreateEmbed {
+        let embed = embed
+            .footer(CreateEmbedFooter::new(format!(
+                "{} {}",
+                self.footer_emoji, BRAND_NAME_SHORT
+            )))
+            .timestamp(Timestamp::now());
+
+        self.footer_emoji = match self.footer_emoji {
+            "⚫" => "⚪",
+            "⚪" => "⚫",
+            _ => "⚫",
+        };
+
+        embed
+    }
+}
+
+/// State kept between the ticks of a server's status loop
+struct ServerStatusState {
+    message: StatusMessageState,
+    downtime_tracker: DowntimeTracker,
+    activity_tracker: ActivityTracker,
+    /// When playtime was last recorded, `None` while the server is offline
+    last_playtime_sample: Option<DateTime<Utc>>,
+    /// Playtime is only recorded between successful polls, which are this far apart
+    poll_interval: Duration,
+    /// The activity text last shown in the bot's presence, to only update it on change
+    presence: Option<String>,
+    resource_monitor: ResourceMonitor,
+    counter_channels: Vec<CounterChannelState>,
+}
+
 #[async_trait]
 impl EventHandler for ReadyHandler {
     async fn ready(&self, ctx: Context, ready: Ready) {
         info!("{} is online!", ready.user.name);
 
-        // Set activity to Do Not Disturb
+        // Set activity to Do Not Disturb until the main server is polled
         ctx.dnd();
 
         let context_arc = Arc::new(ctx);
 
-        tokio::join!(
-            manage_server_status_message(context_arc.clone()),
-            manage_cfx_status_message(context_arc.clone())
-        );
-    }
-}
+        let (servers, report_channel_id, webhook, status_provider, supervisor) = {
+            let client_data = context_arc.data.read().await;
+            let (_, config) = client_data.get::<ClientData>().unwrap();
+            let locked_config = config.read().await;
 
-fn truncate(s: &str, max_chars: usize) -> &str {
-    match s.char_indices().nth(max_chars) {
-        None => s,
-        Some((idx, _)) => &s[..idx],
-    }
-}
+            (
+                locked_config.fivem_servers.clone(),
+                locked_config.report_channel_id,
+                locked_config.webhook.clone(),
+                client_data.get::<StatusProviderKey>().unwrap().clone(),
+                client_data.get::<Supervisor>().unwrap().clone(),
+            )
+        };
 
-fn sanitize_name(name: &str) -> String {
-    name.replace('\\', "\\\\") // for crazy escaping tactics
-        .replace('*', "\\*") // bold, italic
-        .replace('_', "\\_") // underline
-        .replace('~', "\\~") // strikethrough
-        .replace(':', "\\:") // emojis
-        .replace('`', "\\`") // code
+        // `ready` fires again on reconnect, the supervisor skips the jobs that are already running
+
+        // Every server has its own status message, managed by its own job
+        for server in servers {
+            let ctx = context_arc.clone();
+            let status_provider = status_provider.clone();
+
+            supervisor
+                .spawn(&format!("status:{}", server.name), move || {
+                    manage_server_status_message(
+                        ctx.clone(),
+                        status_provider.clone(),
+                        server.clone(),
+                    )
+                })
+                .await;
+        }
+
+        let ctx = context_arc.clone();
+        supervisor
+            .spawn("cfx-status", move || {
+                manage_cfx_status_message(ctx.clone(), status_provider.clone())
+            })
+            .await;
+
+        let ctx = context_arc.clone();
+        supervisor
+            .spawn("restart-warnings", move || {
+                manage_restart_warnings(ctx.clone())
+            })
+            .await;
+
+        if let Some(report_channel_id) = report_channel_id {
+            let ctx = context_arc.clone();
+            supervisor
+                .spawn("activity-reports", move || {
+                    manage_activity_reports(ctx.clone(), report_channel_id)
+                })
+                .await;
+        }
+
+        if let Some(webhook) = webhook {
+            let http = context_arc.http.clone();
+            supervisor
+                .spawn("webhook", move || {
+                    serve_webhooks(http.clone(), webhook.clone())
+                })
+                .await;
+        }
+    }
 }
 
-async fn manage_cfx_status_message(ctx: Arc<Context>) {
+async fn manage_cfx_status_message(ctx: Arc<Context>, status_provider: Arc<dyn StatusProvider>) {
     let client_data = ctx.data.read().await;
     let (_, config) = client_data.get::<ClientData>().unwrap();
 
-    let mut embed = CreateEmbed::new()
+    let embed = CreateEmbed::new()
         .title("CFX Státusz")
         .description(format!("A <#{}> csatornában mindig értesülsz a [**CFX**](https://status.cfx.re) szolgáltatások aktuális státuszáról!", config.read().await.cfx_status_channel_id))
-        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
-        .timestamp(Timestamp::now())
         .color(SUCCESS_COLOR);
 
-    drop(client_data);
-
-    let forever = task::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(10));
-        let mut emoji = "⚫";
+    let mut backoff = {
+        let locked_config = config.read().await;
+        Backoff::new(locked_config.cfx_status_interval, locked_config.max_backoff)
+    };
+    let mut state = StatusMessageState::default();
 
-        loop {
-            interval.tick().await;
+    drop(client_data);
 
-            embed = embed.footer(CreateEmbedFooter::new(format!(
-                "{} {}",
-                emoji, BRAND_NAME_SHORT
-            )));
-            send_or_edit_cfx_status_message(ctx.clone(), embed.clone()).await;
-
-            emoji = match emoji {
-                "⚫" => "⚪",
-                "⚪" => "⚫",
-                _ => "⚫",
-            };
-        }
-    });
+    loop {
+        let success = send_or_edit_cfx_status_message(
+            ctx.clone(),
+            status_provider.as_ref(),
+            embed.clone(),
+            &mut state,
+        )
+        .await;
 
-    forever.await.expect("Expected to spawn task");
+        backoff.record(success);
+        time::sleep(backoff.delay()).await;
+    }
 }
 
-async fn manage_server_status_message(ctx: Arc<Context>) {
+async fn manage_server_status_message(
+    ctx: Arc<Context>,
+    status_provider: Arc<dyn StatusProvider>,
+    server: FivemServer,
+) {
     let client_data = ctx.data.read().await;
     let (_, config) = client_data.get::<ClientData>().unwrap();
 
-    let mut embed = CreateEmbed::new()
-        .title(format!("{} | Szerver Státusz", BRAND_NAME))
-        .description(format!("A <#{}> csatornában mindig értesülsz a szerver aktuális elérhetőségéről és állapotáról!", config.read().await.status_channel_id))
-        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
-        .timestamp(Timestamp::now())
+    let embed = CreateEmbed::new()
+        .title(format!("{} | Szerver Státusz", server.name))
+        .description(format!("A <#{}> csatornában mindig értesülsz a szerver aktuális elérhetőségéről és állapotáról!", server.status_channel_id))
         .color(BRAND_COLOR);
 
+    let mut backoff = {
+        let locked_config = config.read().await;
+        Backoff::new(locked_config.status_interval, locked_config.max_backoff)
+    };
+    let mut state = ServerStatusState {
+        message: StatusMessageState::default(),
+        downtime_tracker: DowntimeTracker::new(config.read().await.downtime_failure_threshold),
+        activity_tracker: ActivityTracker::default(),
+        last_playtime_sample: None,
+        poll_interval: config.read().await.status_interval,
+        presence: None,
+        resource_monitor: ResourceMonitor::default(),
+        counter_channels: config
+            .read()
+            .await
+            .counter_channels(&server.name)
+            .into_iter()
+            .map(CounterChannelState::new)
+            .collect(),
+    };
+
     drop(client_data);
 
-    let forever = task::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(10));
-        let mut emoji = "⚫";
+    loop {
+        let success = send_or_edit_server_status_message(
+            ctx.clone(),
+            status_provider.as_ref(),
+            &server,
+            embed.clone(),
+            &mut state,
+        )
+        .await;
 
-        loop {
-            interval.tick().await;
+        backoff.record(success);
+        time::sleep(backoff.delay()).await;
+    }
+}
 
-            embed = embed.footer(CreateEmbedFooter::new(format!(
-                "{} {}",
-                emoji, BRAND_NAME_SHORT
-            )));
-            send_or_edit_server_status_message(ctx.clone(), embed.clone()).await;
-
-            emoji = match emoji {
-                "⚫" => "⚪",
-                "⚪" => "⚫",
-                _ => "⚫",
-            };
+async fn manage_restart_warnings(ctx: Arc<Context>) {
+    let mut interval = time::interval(Duration::from_secs(10));
+    let mut previous_remaining: Option<TimeDelta> = None;
+
+    loop {
+        interval.tick().await;
+
+        let client_data = ctx.data.read().await;
+        let (_, config) = client_data.get::<ClientData>().unwrap();
+
+        let (remaining, warning_minutes, status_channel_id) = {
+            let locked_config = config.read().await;
+
+            (
+                locked_config
+                    .restart_schedule
+                    .time_until_next_restart(Utc::now()),
+                locked_config.restart_schedule.warning_minutes.clone(),
+                locked_config.main_server().status_channel_id,
+            )
+        };
+
+        drop(client_data);
+
+        let Some(remaining) = remaining else {
+            continue;
+        };
+
+        // Only warn when a threshold is crossed, so a restart of the bot doesn't repeat warnings
+        if let Some(previous_remaining) = previous_remaining {
+            for minutes in warning_minutes {
+                let offset = TimeDelta::minutes(minutes);
+
+                if previous_remaining > offset && remaining <= offset {
+                    send_restart_warning(&ctx, status_channel_id, minutes).await;
+                }
+            }
         }
-    });
 
-    forever.await.expect("Expected to spawn task");
+        previous_remaining = Some(remaining);
+    }
 }
 
-async fn send_or_edit_cfx_status_message(ctx: Arc<Context>, mut embed: CreateEmbed) {
-    let client_data = ctx.data.read().await;
-    let (_, config) = client_data.get::<ClientData>().unwrap();
+async fn manage_activity_reports(ctx: Arc<Context>, report_channel_id: u64) {
+    let mut interval = time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        let client_data = ctx.data.read().await;
+        let (_, config) = client_data.get::<ClientData>().unwrap();
 
-    let response = get_cfx_status().await;
+        let now = Utc::now();
+        let (today, post_time, last_report_date, reports, server_name) = {
+            let locked_config = config.read().await;
+            let schedule = &locked_config.report_schedule;
+            let today = now.with_timezone(&schedule.timezone).date_naive();
 
-    match response {
-        Ok(status) => {
-            embed = embed.fields(vec![("Globális Státusz:", "✅ Elérhető", false)]);
+            (
+                today,
+                schedule.post_time(today),
+                locked_config.data_json.last_report_date,
+                schedule.due_reports(today),
+                locked_config.main_server().name.clone(),
+            )
+        };
 
-            let status_fields = status
-                .components
+        if last_report_date == Some(today) || post_time.is_none_or(|post_time| now < post_time) {
+            continue;
+        }
+
+        // Marked as posted first, so a failing send isn't retried every minute
+        {
+            let mut locked_config = config.write().await;
+            locked_config.data_json.set_last_report_date(today);
+            locked_config.data_json.save();
+        }
+
+        let embeds: Vec<CreateEmbed> = {
+            let history = client_data.get::<PlayerHistory>().unwrap().read().await;
+            let playtime = client_data.get::<PlaytimeStore>().unwrap().read().await;
+            let uptime_log = client_data.get::<UptimeLog>().unwrap().read().await;
+            let submissions = client_data.get::<SubmissionLog>().unwrap().read().await;
+
+            reports
                 .iter()
-                .filter(|c| {
-                    c.name != "Server List Frontend"
-                        && c.name != "RedM"
-                        && c.name != "\"Runtime\""
-                        && c.name != "IDMS"
-                })
-                .map(|c| {
-                    (
-                        &c.name,
-                        match c.status.as_str() {
-                            "operational" => "✔ Elérhető",
-                            _ => "❌ Nem elérhető",
-                        },
-                        true,
+                .map(|window| {
+                    ActivityReport::collect(
+                        &server_name,
+                        window,
+                        &history,
+                        &playtime,
+                        &uptime_log,
+                        &submissions,
                     )
-                });
+                    .embed(&server_name, window)
+                })
+                .collect()
+        };
 
-            embed = embed.fields(status_fields).timestamp(Timestamp::now());
+        drop(client_data);
+
+        if embeds.is_empty() {
+            continue;
         }
-        Err(e) => {
-            error!("Error getting CFX status: {e:?}");
 
-            embed = embed
-                .fields(vec![("Globális Státusz:", "❌ Nem elérhető", true)])
-                .timestamp(Timestamp::now())
-                .color(ERROR_COLOR);
+        if let Some(sent_message) = send_message(
+            &ctx.http,
+            ChannelId::new(report_channel_id),
+            CreateMessage::new().embeds(embeds),
+        )
+        .await
+        {
+            info!(
+                "Sent activity report message with id: {}",
+                sent_message.id.to_string()
+            );
+        }
+    }
+}
+
+async fn send_restart_warning(ctx: &Context, status_channel_id: u64, minutes: i64) {
+    let message = CreateMessage::new().embed(
+        CreateEmbed::new()
+            .title(format!("{} | Szerver Újraindítás", BRAND_NAME))
+            .description(format!("A szerver **{} perc** múlva újraindul!", minutes))
+            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+            .timestamp(Timestamp::now())
+            .color(WARNING_COLOR),
+    );
+
+    if let Some(sent_message) =
+        send_message(&ctx.http, ChannelId::new(status_channel_id), message).await
+    {
+        info!(
+            "Sent restart warning message with id: {}",
+            sent_message.id.to_string()
+        );
+    }
+}
+
+async fn send_downtime_alert(
+    ctx: &Context,
+    server: &FivemServer,
+    alerts_channel_id: Option<u64>,
+    staff_role_id: Option<u64>,
+    transition: Transition,
+) {
+    let embed = match transition {
+        Transition::WentOffline { since } => {
+            info!("Server `{}` went offline", server.name);
+
+            CreateEmbed::new()
+                .title(format!("{} | Szerver Leállás", server.name))
+                .description(format!(
+                    "A szerver nem elérhető! (<t:{}:R> óta)",
+                    since.timestamp()
+                ))
+                .color(ERROR_COLOR)
+        }
+        Transition::CameOnline { outage } => {
+            info!(
+                "Server `{}` is back online after {}s",
+                server.name,
+                outage.num_seconds()
+            );
+
+            CreateEmbed::new()
+                .title(format!("{} | Szerver Helyreállt", server.name))
+                .description(format!(
+                    "A szerver újra elérhető!\nLeállás időtartama: **{}**",
+                    format_duration(outage)
+                ))
+                .color(SUCCESS_COLOR)
+        }
+    };
+
+    send_staff_alert(ctx, alerts_channel_id, staff_role_id, embed).await;
+}
+
+async fn send_resource_alert(
+    ctx: &Context,
+    server: &FivemServer,
+    alerts_channel_id: Option<u64>,
+    staff_role_id: Option<u64>,
+    changes: ResourceChanges,
+) {
+    info!(
+        "Resources of `{}` changed, missing: {:?}, restored: {:?}",
+        server.name, changes.missing, changes.restored
+    );
+
+    let resource_list = |resources: &[String]| {
+        resources
+            .iter()
+            .map(|resource| format!("- `{}`", resource))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let (embed, staff_role_id) = match changes.still_missing.is_empty() {
+        false => (
+            CreateEmbed::new()
+                .title(format!("{} | Hiányzó Resource-ok", server.name))
+                .description(truncate(
+                    &format!(
+                        "A következő resource-ok nem futnak:\n{}",
+                        resource_list(&changes.still_missing)
+                    ),
+                    MAX_DESCRIPTION_LENGTH,
+                ))
+                .color(WARNING_COLOR),
+            staff_role_id,
+        ),
+        // Nobody needs to be pinged when everything is running again
+        true => (
+            CreateEmbed::new()
+                .title(format!("{} | Resource-ok Helyreálltak", server.name))
+                .color(SUCCESS_COLOR),
+            None,
+        ),
+    };
+
+    let embed = match changes.restored.is_empty() {
+        false => embed.field(
+            "Újra futnak:",
+            truncate(&resource_list(&changes.restored), MAX_FIELD_LENGTH),
+            false,
+        ),
+        true => embed,
+    };
+
+    send_staff_alert(ctx, alerts_channel_id, staff_role_id, embed).await;
+}
+
+/// Sends an alert to the alerts channel, if there is one, pinging the staff role if given
+async fn send_staff_alert(
+    ctx: &Context,
+    alerts_channel_id: Option<u64>,
+    staff_role_id: Option<u64>,
+    embed: CreateEmbed,
+) {
+    let Some(alerts_channel_id) = alerts_channel_id else {
+        return;
+    };
+
+    let mut message = CreateMessage::new().embed(
+        embed
+            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+            .timestamp(Timestamp::now()),
+    );
+
+    if let Some(staff_role_id) = staff_role_id {
+        message = message
+            .content(format!("<@&{}>", staff_role_id))
+            .allowed_mentions(CreateAllowedMentions::new().roles(vec![RoleId::new(staff_role_id)]));
+    }
+
+    if let Some(sent_message) =
+        send_message(&ctx.http, ChannelId::new(alerts_channel_id), message).await
+    {
+        info!(
+            "Sent alert message with id: {}",
+            sent_message.id.to_string()
+        );
+    }
+}
+
+async fn send_activity_log(
+    ctx: &Context,
+    server: &FivemServer,
+    activity_log_channel_id: u64,
+    changes: ActivityChanges,
+) {
+    let player_line = |session: &Session| match session.discord_id {
+        Some(discord_id) => format!(
+            "`#{}` {} (<@{}>)",
+            session.server_id,
+            sanitize_name(&session.name),
+            discord_id
+        ),
+        None => format!("`#{}` {}", session.server_id, sanitize_name(&session.name)),
+    };
+
+    let joined = changes
+        .joined
+        .iter()
+        .map(|session| format!("📥 {}", player_line(session)));
+    let left = changes.left.iter().map(|(session, duration)| {
+        format!(
+            "📤 {} *({})*",
+            player_line(session),
+            format_duration(*duration)
+        )
+    });
+
+    let title = format!("{} | Játékos Aktivitás", server.name);
+    let footer = format!(
+        "{} • {} csatlakozott, {} kilépett",
+        BRAND_NAME_SHORT,
+        changes.joined.len(),
+        changes.left.len()
+    );
+    let lines = joined.chain(left).map(|line| line + "\n");
+
+    for descriptions in split_into_messages(lines, title.len() + footer.len()) {
+        let embeds = descriptions
+            .into_iter()
+            .map(|description| {
+                CreateEmbed::new()
+                    .title(&title)
+                    .description(description)
+                    .footer(CreateEmbedFooter::new(&footer))
+                    .timestamp(Timestamp::now())
+                    .color(BRAND_COLOR)
+            })
+            .collect::<Vec<_>>();
+
+        let message = CreateMessage::new().embeds(embeds);
+
+        if let Some(sent_message) =
+            send_message(&ctx.http, ChannelId::new(activity_log_channel_id), message).await
+        {
+            info!(
+                "Sent activity log message with id: {}",
+                sent_message.id.to_string()
+            );
+        }
+    }
+}
+
+/// Splits the lines into embed descriptions, grouped into messages that stay under Discord's limits
+///
+/// `embed_overhead` is the length of the title and footer repeated in every embed.
+fn split_into_messages(
+    lines: impl Iterator<Item = String>,
+    embed_overhead: usize,
+) -> Vec<Vec<String>> {
+    let mut messages: Vec<Vec<String>> = vec![vec![String::new()]];
+    let mut message_length = embed_overhead;
+
+    for line in lines {
+        let descriptions = messages.last_mut().unwrap();
+        let description = descriptions.last_mut().unwrap();
+
+        if description.len() + line.len() <= MAX_DESCRIPTION_LENGTH
+            && message_length + line.len() <= MAX_MESSAGE_EMBEDS_LENGTH
+        {
+            message_length += line.len();
+            *description += &line;
+        } else if descriptions.len() < MAX_EMBEDS_PER_MESSAGE
+            && message_length + embed_overhead + line.len() <= MAX_MESSAGE_EMBEDS_LENGTH
+        {
+            message_length += embed_overhead + line.len();
+            descriptions.push(line);
+        } else {
+            message_length = embed_overhead + line.len();
+            messages.push(vec![line]);
+        }
+    }
+
+    messages
+}
+
+/// Posts a message to the CFX status channel when an incident appears or gets resolved
+async fn announce_cfx_incidents(ctx: &Context, config: &RwLock<Config>, status: &CFXStatus) {
+    let (changes, channel_id) = {
+        let locked_config = config.read().await;
+
+        // The incidents open before the bot first ran are not announced
+        let Some(announced) = &locked_config.data_json.cfx_incidents else {
+            drop(locked_config);
+
+            let mut locked_config = config.write().await;
+            locked_config.data_json.cfx_incidents = Some(
+                diff_incidents(&HashMap::new(), &status.incidents)
+                    .new
+                    .into_iter()
+                    .map(|incident| (incident.id.clone(), incident.name.clone()))
+                    .collect(),
+            );
+            locked_config.data_json.save();
+            return;
+        };
+
+        (
+            diff_incidents(announced, &status.incidents),
+            ChannelId::new(locked_config.cfx_status_channel_id),
+        )
+    };
+
+    if changes.new.is_empty() && changes.resolved.is_empty() {
+        return;
+    }
+
+    let mut announced_incidents = Vec::new();
+    for incident in changes.new {
+        info!("New CFX incident: {}", incident.name);
+
+        let message = CreateMessage::new().embed(
+            new_incident_embed(incident)
+                .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                .timestamp(Timestamp::now()),
+        );
+        // Retried on the next tick if it couldn't be sent
+        if send_message(&ctx.http, channel_id, message).await.is_some() {
+            announced_incidents.push((incident.id.clone(), incident.name.clone()));
+        }
+    }
+
+    let mut resolved_incidents = Vec::new();
+    for (id, name) in changes.resolved {
+        info!("CFX incident resolved: {}", name);
+
+        let message = CreateMessage::new().embed(
+            resolved_incident_embed(&name)
+                .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+                .timestamp(Timestamp::now()),
+        );
+        if send_message(&ctx.http, channel_id, message).await.is_some() {
+            resolved_incidents.push(id);
         }
     }
 
     let mut locked_config = config.write().await;
+    let incidents = locked_config
+        .data_json
+        .cfx_incidents
+        .get_or_insert_with(HashMap::new);
 
-    let channel = ctx
-        .http
-        .get_channel(ChannelId::new(locked_config.cfx_status_channel_id))
-        .await
-        .expect("Expected to find the CFX status channel")
-        .guild()
-        .expect("Expected the CFX status channel to be in a guild");
+    incidents.extend(announced_incidents);
+    for id in resolved_incidents {
+        incidents.remove(&id);
+    }
 
-    match locked_config.data_json.cfx_status_message_id {
-        Some(id) => {
-            let message = EditMessage::new().embed(embed);
+    locked_config.data_json.save();
+}
+
+/// Returns whether the CFX status could be fetched
+async fn send_or_edit_cfx_status_message(
+    ctx: Arc<Context>,
+    status_provider: &dyn StatusProvider,
+    mut embed: CreateEmbed,
+    state: &mut StatusMessageState,
+) -> bool {
+    let client_data = ctx.data.read().await;
+    let (_, config) = client_data.get::<ClientData>().unwrap();
+
+    let response = status_provider.get_cfx_status().await;
+    let success = response.is_ok();
+
+    match &response {
+        Ok(status) => announce_cfx_incidents(&ctx, config, status).await,
+        Err(e) => error!("Error getting CFX status: {e:?}"),
+    }
+
+    embed = add_cfx_status_fields(embed, &response, &config.read().await.cfx_component_filter);
+
+    let mut locked_config = config.write().await;
+
+    if locked_config.data_json.cfx_status_message_id.is_some() && state.is_unchanged(&embed) {
+        return success;
+    }
+
+    let rendered_embed = embed.clone();
+    let embed = state.finish_embed(embed);
+
+    let channel_id = ChannelId::new(locked_config.cfx_status_channel_id);
+
+    if let Some(id) = locked_config.data_json.cfx_status_message_id {
+        let message = EditMessage::new().embed(embed.clone());
+
+        match edit_tracked_message(&ctx.http, channel_id, id, message).await {
+            Ok(edited_message) => {
+                state.last_embed = Some(rendered_embed);
 
-            if let Some(edited_message) = edit_message(&ctx.http, channel.into(), id, message).await
-            {
                 info!(
                     "Edited CFX status message with id: {}",
                     edited_message.id.to_string()
                 );
+
+                return success;
+            }
+            // The message was deleted, so a new one is posted instead
+            Err(EditError::Missing) => {
+                locked_config.data_json.clear_cfx_status_message_id();
+                locked_config.data_json.save();
             }
+            Err(EditError::Other) => return success,
         }
-        None => {
-            let message = CreateMessage::new().embed(embed);
+    }
 
-            if let Some(sent_message) = send_message(&ctx.http, channel.into(), message).await {
-                locked_config
-                    .data_json
-                    .set_cfx_status_message_id(u64::from(sent_message.id));
-                locked_config.data_json.save();
+    let message = CreateMessage::new().embed(embed);
 
-                info!(
-                    "Created new CFX status message with id: {}",
-                    sent_message.id.to_string()
-                );
-            }
+    if let Some(sent_message) = send_message(&ctx.http, channel_id, message).await {
+        locked_config
+            .data_json
+            .set_cfx_status_message_id(u64::from(sent_message.id));
+        locked_config.data_json.save();
+        state.last_embed = Some(rendered_embed);
+
+        info!(
+            "Created new CFX status message with id: {}",
+            sent_message.id.to_string()
+        );
+    }
+
+    success
+}
+
+fn update_presence(
+    ctx: &Context,
+    response: &Result<(Box<[Player]>, ServerInfo)>,
+    state: &mut ServerStatusState,
+) {
+    let presence = match response {
+        _ if state.downtime_tracker.is_offline() => String::from("❌ A szerver nem elérhető"),
+        Ok((players, server_info)) => {
+            format!("{}/{} játékos", players.len(), server_info.vars.max_players)
         }
+        // Keep the last presence until the failure threshold is reached
+        Err(_) => return,
     };
+
+    // Presence updates are rate limited, so only send them when something changed
+    if state.presence.as_ref() == Some(&presence) {
+        return;
+    }
+
+    if state.downtime_tracker.is_offline() {
+        ctx.set_presence(
+            Some(ActivityData::custom(&presence)),
+            OnlineStatus::DoNotDisturb,
+        );
+    } else {
+        ctx.set_presence(
+            Some(ActivityData::watching(&presence)),
+            OnlineStatus::Online,
+        );
+    }
+
+    state.presence = Some(presence);
+}
+
+async fn update_counter_channels(
+    ctx: &Context,
+    response: &Result<(Box<[Player]>, ServerInfo)>,
+    queue_length: Option<u32>,
+    state: &mut ServerStatusState,
+) {
+    let values = match response {
+        _ if state.downtime_tracker.is_offline() => CounterValues {
+            online: false,
+            players: 0,
+            max_players: "0",
+            queue_length: Some(0),
+        },
+        Ok((players, server_info)) => CounterValues {
+            online: true,
+            players: players.len(),
+            max_players: &server_info.vars.max_players,
+            queue_length,
+        },
+        // Keep the last values until the failure threshold is reached
+        Err(_) => return,
+    };
+
+    let now = Utc::now();
+
+    for counter_channel in &mut state.counter_channels {
+        let name = render_counter(&counter_channel.channel.template, &values);
+
+        if !counter_channel.should_rename(&name, now) {
+            continue;
+        }
+
+        match ChannelId::new(counter_channel.channel.channel_id)
+            .edit(&ctx.http, EditChannel::new().name(&name))
+            .await
+        {
+            Ok(_) => counter_channel.record_rename(&name, now),
+            Err(e) => error!("Error renaming counter channel: {e:?}"),
+        }
+    }
 }
 
-async fn send_or_edit_server_status_message(ctx: Arc<Context>, mut embed: CreateEmbed) {
+/// Returns whether the players of the server could be fetched
+async fn send_or_edit_server_status_message(
+    ctx: Arc<Context>,
+    status_provider: &dyn StatusProvider,
+    server: &FivemServer,
+    mut embed: CreateEmbed,
+    state: &mut ServerStatusState,
+) -> bool {
     let client_data = ctx.data.read().await;
     let (_, config) = client_data.get::<ClientData>().unwrap();
 
-    let response = get_players(&ctx).await;
+    let response = status_provider.get_players(&server.endpoint).await;
+    let success = response.is_ok();
+    let queue_length = fetch_queue_length(status_provider, server, &response).await;
+
+    let transition = match response {
+        Ok(_) => state.downtime_tracker.record_success(),
+        Err(_) => state.downtime_tracker.record_failure(),
+    };
+
+    {
+        let mut uptime_log = client_data.get::<UptimeLog>().unwrap().write().await;
 
-    match response {
+        match (&response, &transition) {
+            (Ok(_), _) => uptime_log.record(&server.name, true, Utc::now()),
+            (Err(_), Some(Transition::WentOffline { since })) => {
+                uptime_log.record(&server.name, false, *since)
+            }
+            _ => {}
+        }
+    }
+
+    let went_offline = matches!(transition, Some(Transition::WentOffline { .. }));
+
+    if let Some(transition) = transition {
+        let (alerts_channel_id, staff_role_id) = {
+            let locked_config = config.read().await;
+            (locked_config.alerts_channel_id, locked_config.staff_role_id)
+        };
+
+        send_downtime_alert(&ctx, server, alerts_channel_id, staff_role_id, transition).await;
+    }
+
+    // The bot has a single presence, so only the main server is shown in it
+    if config.read().await.main_server().name == server.name {
+        update_presence(&ctx, &response, state);
+    }
+
+    update_counter_channels(&ctx, &response, queue_length, state).await;
+
+    let next_restart = next_restart_text(&config.read().await.restart_schedule);
+
+    match &response {
         Ok((players, server_info)) => {
-            embed = embed.fields(vec![
-                ("Szerver Státusz:", "✅ Elérhető", true),
-                (
-                    "Elérhető Játékosok:",
-                    &format!("{}/{}", players.len(), server_info.vars.max_players),
-                    true,
-                ),
-                ("Következő Újraindításig:", "6 óra 15 perc", true),
-            ]);
-
-            if players.len() > 0 {
-                let mut player_values = [
-                    String::from("**Játékosok:**\n"),
-                    String::from(""),
-                    String::from(""),
-                ];
-
-                for (i, player) in players.iter().enumerate() {
-                    player_values[(i + 1) % 3] += &format!(
-                        "{} *({}ms)*\n",
-                        sanitize_name(truncate(&player.name, 12)),
-                        player.ping
+            client_data
+                .get::<PlayerHistory>()
+                .unwrap()
+                .write()
+                .await
+                .record(
+                    &server.name,
+                    players.len() as u32,
+                    server_info.vars.max_players.parse().unwrap_or(0),
+                );
+
+            let now = Utc::now();
+            if let Some(last_playtime_sample) = state.last_playtime_sample {
+                client_data
+                    .get::<PlaytimeStore>()
+                    .unwrap()
+                    .write()
+                    .await
+                    .record(
+                        &server.name,
+                        players,
+                        now - last_playtime_sample,
+                        state.poll_interval,
                     );
-                }
+            }
+            state.last_playtime_sample = Some(now);
 
-                let player_fields = player_values
-                    .iter()
-                    .filter(|s| !s.is_empty())
-                    .map(|s| ("\u{200b}", s, true));
+            if let Some(changes) = state
+                .resource_monitor
+                .update(&server.required_resources, &server_info.resources)
+            {
+                let (alerts_channel_id, staff_role_id) = {
+                    let locked_config = config.read().await;
+                    (locked_config.alerts_channel_id, locked_config.staff_role_id)
+                };
+
+                send_resource_alert(&ctx, server, alerts_channel_id, staff_role_id, changes).await;
+            }
+
+            if let Some(changes) = state.activity_tracker.update(players) {
+                let activity_log_channel_id = config.read().await.activity_log_channel_id;
 
-                embed = embed.fields(player_fields).timestamp(Timestamp::now());
+                if let Some(activity_log_channel_id) = activity_log_channel_id {
+                    send_activity_log(&ctx, server, activity_log_channel_id, changes).await;
+                }
             }
         }
         Err(e) => {
             error!("Error getting players: {e:?}");
 
-            embed = embed
-                .fields(vec![("Szerver Státusz:", "❌ Nem elérhető", true)])
-                .timestamp(Timestamp::now())
-                .color(ERROR_COLOR);
+            state.last_playtime_sample = None;
+
+            if went_offline {
+                if let Some(changes) = state.activity_tracker.end_sessions() {
+                    let activity_log_channel_id = config.read().await.activity_log_channel_id;
+
+                    if let Some(activity_log_channel_id) = activity_log_channel_id {
+                        send_activity_log(&ctx, server, activity_log_channel_id, changes).await;
+                    }
+                }
+            }
         }
     }
 
-    let mut locked_config = config.write().await;
+    let info_fields = {
+        let status_info_fields = config.read().await.status_info_fields.clone();
 
-    let channel = ctx
-        .http
-        .get_channel(ChannelId::new(locked_config.status_channel_id))
+        fetch_info_fields(
+            status_provider,
+            &server.endpoint,
+            &status_info_fields,
+            &response,
+        )
         .await
-        .expect("Expected to find the status channel")
-        .guild()
-        .expect("Expected the status channel to be in a guild");
-
-    match locked_config.data_json.status_message_id {
-        Some(id) => {
-            let message =
-                EditMessage::new()
-                    .embed(embed)
-                    .components(vec![CreateActionRow::Buttons(vec![
-                        CreateButton::new_link(BRAND_WEBSITE).label("Weboldal"),
-                        CreateButton::new_link(format!(
-                            "https://discord.com/channels/{}/{}",
-                            locked_config.guild_id, locked_config.help_channel_id
-                        ))
-                        .label("Segítségkérés"),
-                    ])]);
-
-            if let Some(edited_message) = edit_message(&ctx.http, channel.into(), id, message).await
-            {
+    };
+
+    embed = add_server_status_fields(embed, &response, &next_restart, queue_length, &info_fields);
+    embed = add_connect_field(embed, server);
+
+    let mut locked_config = config.write().await;
+
+    let status_message_id = locked_config
+        .data_json
+        .status_message_ids
+        .get(&server.name)
+        .copied();
+
+    if status_message_id.is_some() && state.message.is_unchanged(&embed) {
+        return success;
+    }
+
+    let rendered_embed = embed.clone();
+    let embed = state.message.finish_embed(embed);
+
+    let channel_id = ChannelId::new(server.status_channel_id);
+    let components = link_button_rows(&locked_config.status_buttons(server));
+
+    if let Some(id) = status_message_id {
+        let message = EditMessage::new()
+            .embed(embed.clone())
+            .components(components.clone());
+
+        match edit_tracked_message(&ctx.http, channel_id, id, message).await {
+            Ok(edited_message) => {
+                state.message.last_embed = Some(rendered_embed);
+
                 info!(
-                    "Edited status message with id: {}",
+                    "Edited status message of `{}` with id: {}",
+                    server.name,
                     edited_message.id.to_string()
                 );
+
+                return success;
             }
-        }
-        None => {
-            let message =
-                CreateMessage::new()
-                    .embed(embed)
-                    .components(vec![CreateActionRow::Buttons(vec![
-                        CreateButton::new_link(BRAND_WEBSITE).label("Weboldal"),
-                        CreateButton::new_link(format!(
-                            "https://discord.com/channels/{}/{}",
-                            locked_config.guild_id, locked_config.help_channel_id
-                        ))
-                        .label("Segítségkérés"),
-                    ])]);
-
-            if let Some(sent_message) = send_message(&ctx.http, channel.into(), message).await {
+            // The message was deleted, so a new one is posted instead
+            Err(EditError::Missing) => {
                 locked_config
                     .data_json
-                    .set_status_message_id(u64::from(sent_message.id));
+                    .clear_status_message_id(&server.name);
                 locked_config.data_json.save();
-
-                info!(
-                    "Created new status message with id: {}",
-                    sent_message.id.to_string()
-                );
             }
+            Err(EditError::Other) => return success,
         }
-    };
-}
-
-#[derive(Deserialize)]
-struct Player {
-    name: String,
-    ping: u32,
-}
-
-#[derive(Deserialize)]
-struct ServerInfo {
-    vars: Vars,
-}
-
-#[derive(Deserialize)]
-struct Vars {
-    #[serde(rename = "sv_maxClients")]
-    max_players: String,
-}
-
-async fn get_players(ctx: &Context) -> Result<(Box<[Player]>, ServerInfo)> {
-    let client_data = ctx.data.read().await;
-    let (_, config) = client_data.get::<ClientData>().unwrap();
+    }
 
-    let fivem_ip = &config.read().await.fivem_ip;
+    let message = CreateMessage::new().embed(embed).components(components);
 
-    let players = reqwest::get(format!("{}/players.json", fivem_ip))
-        .await?
-        .json::<Box<[Player]>>()
-        .await?;
+    if let Some(sent_message) = send_message(&ctx.http, channel_id, message).await {
+        locked_config
+            .data_json
+            .set_status_message_id(&server.name, u64::from(sent_message.id));
+        locked_config.data_json.save();
+        state.message.last_embed = Some(rendered_embed);
 
-    let server_info = reqwest::get(format!("{}/info.json", fivem_ip))
-        .await?
-        .json::<ServerInfo>()
-        .await?;
+        info!(
+            "Created new status message of `{}` with id: {}",
+            server.name,
+            sent_message.id.to_string()
+        );
+    }
 
-    Ok((players, server_info))
+    success
 }
 
-#[derive(Deserialize)]
-struct CFXStatus {
-    components: Box<[Component]>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-#[derive(Deserialize)]
-struct Component {
-    name: String,
-    status: String,
-}
+    #[test]
+    fn splits_activity_log_under_discord_limits() {
+        let line = format!("📤 {} *(1 óra)*\n", "x".repeat(80));
+        let lines = std::iter::repeat_n(line.clone(), 500);
+
+        let messages = split_into_messages(lines, 100);
 
-async fn get_cfx_status() -> Result<CFXStatus> {
-    let response = reqwest::get("https://status.cfx.re/api/v2/summary.json")
-        .await?
-        .json::<CFXStatus>()
-        .await?;
+        let total_lines: usize = messages
+            .iter()
+            .flatten()
+            .map(|description| description.matches('\n').count())
+            .sum();
+        assert_eq!(total_lines, 500);
 
-    Ok(response)
+        for descriptions in &messages {
+            assert!(descriptions.len() <= MAX_EMBEDS_PER_MESSAGE);
+            assert!(descriptions
+                .iter()
+                .all(|description| description.len() <= MAX_DESCRIPTION_LENGTH));
+            assert!(
+                descriptions
+                    .iter()
+                    .map(|description| description.len() + 100)
+                    .sum::<usize>()
+                    <= MAX_MESSAGE_EMBEDS_LENGTH
+            );
+        }
+    }
 }
@@ -4,6 +4,8 @@ use serenity::{
 };
 use tracing::{error, info};
 
+use crate::music::update_music_panel;
+
 pub struct VoiceStateUpdateHandler;
 
 #[async_trait]
@@ -52,5 +54,7 @@ async fn leave_channel(ctx: &Context, guild_id: GuildId) {
     // Throws error: `NoCall` for some reason, but successfully disconnects from the call
     let _ = manager.remove(guild_id).await;
 
+    update_music_panel(&ctx.http, &ctx.data, &manager, guild_id).await;
+
     info!("Left voice channel due to member inactivity");
 }
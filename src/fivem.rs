@@ -0,0 +1,88 @@
+use anyhow::anyhow;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+pub struct Player {
+    /// Server id of the player, shown in-game
+    pub id: u32,
+    pub name: String,
+    pub ping: u32,
+    /// e.g. `license:...`, `steam:...`, `discord:...`
+    #[serde(default)]
+    pub identifiers: Vec<String>,
+}
+
+impl Player {
+    /// The Discord account linked to the player's FiveM account, if any
+    pub fn discord_id(&self) -> Option<u64> {
+        self.identifiers
+            .iter()
+            .find_map(|identifier| identifier.strip_prefix("discord:"))
+            .and_then(|id| id.parse().ok())
+    }
+
+    /// An identifier that stays the same across sessions, unlike the server id
+    pub fn stable_identifier(&self) -> String {
+        ["license:", "license2:", "fivem:", "discord:", "steam:"]
+            .iter()
+            .find_map(|prefix| {
+                self.identifiers
+                    .iter()
+                    .find(|identifier| identifier.starts_with(prefix))
+            })
+            .cloned()
+            .unwrap_or(format!("name:{}", self.name))
+    }
+}
+
+/// `info.json` of the server
+#[derive(Deserialize)]
+pub struct ServerInfo {
+    pub vars: Vars,
+    #[serde(default)]
+    pub resources: Vec<String>,
+    /// e.g. `FXServer-master SERVER v1.0.0.7290 linux`
+    pub server: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Vars {
+    #[serde(rename = "sv_maxClients")]
+    pub max_players: String,
+    #[serde(rename = "sv_projectName")]
+    pub project_name: Option<String>,
+    #[serde(rename = "sv_enforceGameBuild")]
+    pub game_build: Option<String>,
+    #[serde(rename = "onesync_enabled")]
+    pub onesync_enabled: Option<String>,
+    pub tags: Option<String>,
+    pub locale: Option<String>,
+}
+
+/// `dynamic.json` of the server
+#[derive(Deserialize)]
+pub struct DynamicInfo {
+    /// May contain FiveM color codes, e.g. `^1`
+    pub hostname: String,
+    pub gametype: String,
+    pub mapname: String,
+}
+
+/// Reads the queue length from a JSON document by a JSON pointer (e.g. `/queue/count`) or a top level key
+pub fn parse_queue_length(json: &Value, field: &str) -> Result<u32> {
+    let value = match field.starts_with('/') {
+        true => json.pointer(field),
+        false => json.get(field),
+    }
+    .ok_or_else(|| anyhow!("`{field}` is missing from the queue response"))?;
+
+    match value {
+        Value::Number(number) => number.as_u64().map(|length| length as u32),
+        Value::String(string) => string.trim().parse().ok(),
+        Value::Array(array) => Some(array.len() as u32),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("`{field}` is not a queue length: {value}"))
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::prelude::*;
+
+use crate::report::REPORT_RETENTION;
+use crate::utils::load_json_file;
+use crate::utils::save_json_file;
+
+const HISTORY_FILE: &str = "./player_history.json";
+
+/// Minimum time between two recorded samples, the status loop polls a lot more often
+const SAMPLE_INTERVAL: TimeDelta = TimeDelta::minutes(1);
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct PlayerSample {
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub players: u32,
+    #[serde(rename = "maxPlayers")]
+    pub max_players: u32,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PlayerHistory {
+    /// Only read to migrate from the time a single server was supported
+    #[serde(rename = "samples", default, skip_serializing)]
+    pub legacy_samples: Vec<PlayerSample>,
+    /// Samples by server name
+    #[serde(default)]
+    pub servers: HashMap<String, Vec<PlayerSample>>,
+}
+
+impl TypeMapKey for PlayerHistory {
+    type Value = Arc<RwLock<PlayerHistory>>;
+}
+
+impl PlayerHistory {
+    /// Loads the history, moving the samples of a single server file to the main server
+    pub fn load(main_server_name: &str) -> Self {
+        let mut history: Self = load_json_file(HISTORY_FILE);
+
+        if !history.legacy_samples.is_empty() {
+            let legacy_samples = std::mem::take(&mut history.legacy_samples);
+            history
+                .servers
+                .entry(main_server_name.to_string())
+                .or_insert(legacy_samples);
+            history.save();
+        }
+
+        history
+    }
+
+    pub fn save(&self) {
+        save_json_file(HISTORY_FILE, self);
+    }
+
+    /// Records a sample and persists the history, unless the last sample is too recent
+    pub fn record(&mut self, server_name: &str, players: u32, max_players: u32) {
+        let now = Utc::now().timestamp();
+        let samples = self.servers.entry(server_name.to_string()).or_default();
+
+        if let Some(last) = samples.last() {
+            if now - last.timestamp < SAMPLE_INTERVAL.num_seconds() {
+                return;
+            }
+        }
+
+        samples.push(PlayerSample {
+            timestamp: now,
+            players,
+            max_players,
+        });
+        samples.retain(|sample| now - sample.timestamp <= REPORT_RETENTION.num_seconds());
+
+        self.save();
+    }
+
+    pub fn samples_since(&self, server_name: &str, since: TimeDelta) -> Vec<PlayerSample> {
+        let from = Utc::now().timestamp() - since.num_seconds();
+
+        self.servers
+            .get(server_name)
+            .into_iter()
+            .flatten()
+            .filter(|sample| sample.timestamp >= from)
+            .copied()
+            .collect()
+    }
+
+    /// Samples recorded in `from..to`
+    pub fn samples_between(
+        &self,
+        server_name: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<PlayerSample> {
+        self.servers
+            .get(server_name)
+            .into_iter()
+            .flatten()
+            .filter(|sample| (from.timestamp()..to.timestamp()).contains(&sample.timestamp))
+            .copied()
+            .collect()
+    }
+}
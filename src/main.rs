@@ -1,3 +1,4 @@
+use events::interaction_create::InteractionHandler;
 use events::voice_state_update::VoiceStateUpdateHandler;
 use poise::samples::register_in_guild;
 use songbird::SerenityInit;
@@ -21,8 +22,55 @@ use events::ready::ReadyHandler;
 mod commands;
 use commands::Data;
 
+mod activity;
+
+mod backoff;
+
+mod cfx;
+
+mod chart;
+
+mod counters;
+
+mod downtime;
+
+mod fivem;
+
+mod history;
+use history::PlayerHistory;
+
+mod music;
+
+mod playtime;
+use playtime::PlaytimeStore;
+
+mod provider;
+use provider::{HttpStatusProvider, StatusProviderKey};
+
+mod report;
+
+mod resources;
+
+mod restart;
+
+mod status;
+
+mod submissions;
+use submissions::SubmissionLog;
+
+mod suggestions;
+use suggestions::SuggestionStore;
+
+mod supervisor;
+use supervisor::Supervisor;
+
+mod uptime;
+use uptime::UptimeLog;
+
 pub mod utils;
 
+mod webhook;
+
 pub struct ClientData {}
 
 impl TypeMapKey for ClientData {
@@ -48,6 +96,8 @@ async fn main() {
     let token = env::var("DISCORD_TOKEN").expect("Expected `DISCORD_TOKEN` in the environment");
 
     let config = Config::new();
+    let http_timeout = config.http_timeout;
+    let main_server_name = config.main_server().name.clone();
     let config_mutex = Arc::new(RwLock::new(config));
 
     let intents = GatewayIntents::GUILDS
@@ -65,6 +115,18 @@ async fn main() {
                 commands::skip(),
                 commands::pause(),
                 commands::resume(),
+                commands::musicpanel(),
+                commands::stats(),
+                commands::uptime(),
+                commands::status(),
+                commands::serverinfo(),
+                commands::health(),
+                commands::players(),
+                commands::whois(),
+                commands::online(),
+                commands::playtime(),
+                commands::leaderboard(),
+                commands::suggestion(),
             ],
             ..Default::default()
         })
@@ -90,14 +152,28 @@ async fn main() {
         .event_handler(ReadyHandler)
         .event_handler(MessageHandler)
         .event_handler(VoiceStateUpdateHandler)
+        .event_handler(InteractionHandler)
         .framework(poise_framework)
         .register_songbird()
         .await
         .expect("Expected to create client");
 
+    let supervisor = Arc::new(Supervisor::default());
+
     {
         let mut data = client.data.write().await;
         data.insert::<ClientData>((client.shard_manager.clone(), config_mutex.clone()));
+        data.insert::<PlayerHistory>(Arc::new(RwLock::new(PlayerHistory::load(
+            &main_server_name,
+        ))));
+        data.insert::<UptimeLog>(Arc::new(RwLock::new(UptimeLog::load(&main_server_name))));
+        data.insert::<PlaytimeStore>(Arc::new(RwLock::new(PlaytimeStore::load())));
+        data.insert::<SubmissionLog>(Arc::new(RwLock::new(SubmissionLog::load())));
+        data.insert::<SuggestionStore>(Arc::new(RwLock::new(SuggestionStore::load())));
+        data.insert::<Supervisor>(supervisor.clone());
+        data.insert::<StatusProviderKey>(Arc::new(HttpStatusProvider::new(
+            utils::build_http_client(http_timeout),
+        )));
     }
 
     let shard_manager = client.shard_manager.clone();
@@ -106,6 +182,7 @@ async fn main() {
         tokio::signal::ctrl_c()
             .await
             .expect("Expected to listen for ctrl-c");
+        supervisor.shutdown();
         shard_manager.shutdown_all().await;
     });
 
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use serenity::all::ButtonStyle;
+use serenity::all::ChannelId;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::EditMessage;
+use serenity::all::GuildId;
+use serenity::all::ReactionType;
+use serenity::all::Timestamp;
+use serenity::async_trait;
+use serenity::http::Http;
+use serenity::prelude::*;
+use songbird::tracks::LoopState;
+use songbird::tracks::PlayMode;
+use songbird::Call;
+use songbird::Event;
+use songbird::EventContext;
+use songbird::EventHandler as VoiceEventHandler;
+use songbird::Songbird;
+use tracing::info;
+
+use crate::utils::edit_message;
+use crate::ClientData;
+use crate::BRAND_COLOR;
+use crate::BRAND_NAME_SHORT;
+
+pub const MUSIC_PLAY_PAUSE: &str = "music_play_pause";
+pub const MUSIC_SKIP: &str = "music_skip";
+pub const MUSIC_STOP: &str = "music_stop";
+pub const MUSIC_SHUFFLE: &str = "music_shuffle";
+pub const MUSIC_LOOP: &str = "music_loop";
+pub const MUSIC_VOLUME_DOWN: &str = "music_volume_down";
+pub const MUSIC_VOLUME_UP: &str = "music_volume_up";
+
+pub const VOLUME_STEP: f32 = 0.1;
+pub const MAX_VOLUME: f32 = 2.0;
+
+/// Title of a queued track, stored in the track's typemap when it is enqueued
+pub struct TrackTitle;
+
+impl TypeMapKey for TrackTitle {
+    type Value = String;
+}
+
+/// Builds the embed and buttons of the music panel from the current state of the call
+pub async fn create_music_panel(
+    handler_lock: Option<Arc<Mutex<Call>>>,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let mut embed = CreateEmbed::new()
+        .title("Music Panel")
+        .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+        .timestamp(Timestamp::now())
+        .color(BRAND_COLOR);
+
+    let current = match &handler_lock {
+        Some(handler_lock) => {
+            let handler = handler_lock.lock().await;
+            let queue = handler.queue();
+
+            queue.current().map(|track| (track, queue.len()))
+        }
+        None => None,
+    };
+
+    let mut playing = false;
+
+    match current {
+        Some((track, queue_len)) => {
+            let title = track
+                .typemap()
+                .read()
+                .await
+                .get::<TrackTitle>()
+                .cloned()
+                .unwrap_or(String::from("Unknown track"));
+
+            embed = embed.description(format!("**Now playing:** {title}"));
+
+            if let Ok(info) = track.get_info().await {
+                playing = info.playing == PlayMode::Play;
+
+                embed = embed.fields(vec![
+                    (
+                        "Status",
+                        if playing {
+                            "▶️ Playing"
+                        } else {
+                            "⏸️ Paused"
+                        }
+                        .to_string(),
+                        true,
+                    ),
+                    (
+                        "Loop",
+                        match info.loops {
+                            LoopState::Infinite => "🔁 On",
+                            LoopState::Finite(_) => "Off",
+                        }
+                        .to_string(),
+                        true,
+                    ),
+                    ("Volume", format!("{:.0}%", info.volume * 100.0), true),
+                    ("Queue", format!("{} track(s)", queue_len), true),
+                ]);
+            }
+        }
+        None => {
+            embed = embed.description("Nothing is playing. Use `/play` to add a song!");
+        }
+    }
+
+    let components = vec![
+        CreateActionRow::Buttons(vec![
+            CreateButton::new(MUSIC_PLAY_PAUSE)
+                .emoji(ReactionType::Unicode(String::from(if playing {
+                    "⏸️"
+                } else {
+                    "▶️"
+                })))
+                .style(ButtonStyle::Primary),
+            CreateButton::new(MUSIC_SKIP)
+                .emoji(ReactionType::Unicode(String::from("⏭️")))
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(MUSIC_STOP)
+                .emoji(ReactionType::Unicode(String::from("⏹️")))
+                .style(ButtonStyle::Danger),
+            CreateButton::new(MUSIC_SHUFFLE)
+                .emoji(ReactionType::Unicode(String::from("🔀")))
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(MUSIC_LOOP)
+                .emoji(ReactionType::Unicode(String::from("🔁")))
+                .style(ButtonStyle::Secondary),
+        ]),
+        CreateActionRow::Buttons(vec![
+            CreateButton::new(MUSIC_VOLUME_DOWN)
+                .emoji(ReactionType::Unicode(String::from("🔉")))
+                .style(ButtonStyle::Secondary),
+            CreateButton::new(MUSIC_VOLUME_UP)
+                .emoji(ReactionType::Unicode(String::from("🔊")))
+                .style(ButtonStyle::Secondary),
+        ]),
+    ];
+
+    (embed, components)
+}
+
+/// Edits the music panel message (if one was posted with `/musicpanel`) to match the call
+pub async fn update_music_panel(
+    http: &Http,
+    data: &RwLock<TypeMap>,
+    manager: &Songbird,
+    guild_id: GuildId,
+) {
+    let client_data = data.read().await;
+    let (_, config) = client_data.get::<ClientData>().unwrap();
+
+    let (channel_id, message_id) = {
+        let locked_config = config.read().await;
+
+        match (
+            locked_config.data_json.music_panel_channel_id,
+            locked_config.data_json.music_panel_message_id,
+        ) {
+            (Some(channel_id), Some(message_id)) => (channel_id, message_id),
+            _ => return,
+        }
+    };
+
+    drop(client_data);
+
+    let (embed, components) = create_music_panel(manager.get(guild_id)).await;
+    let message = EditMessage::new().embed(embed).components(components);
+
+    if let Some(edited_message) =
+        edit_message(http, ChannelId::new(channel_id), message_id, message).await
+    {
+        info!(
+            "Edited music panel message with id: {}",
+            edited_message.id.to_string()
+        );
+    }
+}
+
+/// Refreshes the music panel whenever a track starts or ends
+pub struct TrackChangeNotifier {
+    pub http: Arc<Http>,
+    pub data: Arc<RwLock<TypeMap>>,
+    pub manager: Arc<Songbird>,
+    pub guild_id: GuildId,
+}
+
+#[async_trait]
+impl VoiceEventHandler for TrackChangeNotifier {
+    async fn act(&self, _ctx: &EventContext<'_>) -> Option<Event> {
+        let http = self.http.clone();
+        let data = self.data.clone();
+        let manager = self.manager.clone();
+        let guild_id = self.guild_id;
+
+        // The queue advances in the same event dispatch, so read it once the dispatch is done
+        tokio::spawn(async move {
+            update_music_panel(&http, &data, &manager, guild_id).await;
+        });
+
+        None
+    }
+}
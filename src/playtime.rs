@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::prelude::*;
+
+use crate::fivem::Player;
+use crate::utils::load_json_file;
+use crate::utils::save_json_file;
+
+const PLAYTIME_FILE: &str = "./playtime.json";
+
+/// Minimum time between two saves, playtime is recorded on every status loop tick
+const SAVE_INTERVAL: TimeDelta = TimeDelta::minutes(1);
+/// How much longer than the poll interval the gap between two recordings may be, for the time a poll takes
+const POLL_SLACK: TimeDelta = TimeDelta::minutes(1);
+/// Daily playtime older than this is dropped, the total is kept forever
+const DAILY_RETENTION: TimeDelta = TimeDelta::days(31);
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerPlaytime {
+    /// The last name the player was seen with
+    pub name: String,
+    #[serde(rename = "discordId")]
+    pub discord_id: Option<u64>,
+    #[serde(rename = "totalSeconds")]
+    pub total_seconds: i64,
+    /// Seconds played by UTC day
+    pub days: BTreeMap<NaiveDate, i64>,
+}
+
+impl PlayerPlaytime {
+    /// Seconds played in the given number of days, including today
+    pub fn seconds_in_last_days(&self, days: i64, now: DateTime<Utc>) -> i64 {
+        let from = now.date_naive() - TimeDelta::days(days - 1);
+
+        self.days.range(from..).map(|(_, seconds)| seconds).sum()
+    }
+
+    /// Seconds played on the UTC days in `from..to`
+    pub fn seconds_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        self.days.range(from..to).map(|(_, seconds)| seconds).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct PlaytimeStore {
+    /// Playtime by server name, then by the stable identifier of the player
+    pub servers: HashMap<String, HashMap<String, PlayerPlaytime>>,
+    #[serde(skip)]
+    last_saved: Option<DateTime<Utc>>,
+}
+
+impl TypeMapKey for PlaytimeStore {
+    type Value = Arc<RwLock<PlaytimeStore>>;
+}
+
+impl PlaytimeStore {
+    pub fn load() -> Self {
+        load_json_file(PLAYTIME_FILE)
+    }
+
+    pub fn save(&mut self) {
+        self.last_saved = Some(Utc::now());
+        save_json_file(PLAYTIME_FILE, self);
+    }
+
+    /// Adds `elapsed` to the playtime of everyone on the server, counting at most a bit more than `poll_interval`
+    pub fn record(
+        &mut self,
+        server_name: &str,
+        players: &[Player],
+        elapsed: TimeDelta,
+        poll_interval: Duration,
+    ) {
+        let max_elapsed = TimeDelta::seconds(poll_interval.as_secs() as i64) + POLL_SLACK;
+        let elapsed = elapsed.min(max_elapsed);
+        let now = Utc::now();
+        let today = now.date_naive();
+        let oldest_day = today - DAILY_RETENTION;
+        let server_playtime = self.servers.entry(server_name.to_string()).or_default();
+
+        for player in players {
+            let playtime = server_playtime
+                .entry(player.stable_identifier())
+                .or_insert_with(|| PlayerPlaytime {
+                    name: player.name.clone(),
+                    discord_id: None,
+                    total_seconds: 0,
+                    days: BTreeMap::new(),
+                });
+
+            playtime.name.clone_from(&player.name);
+            playtime.discord_id = player.discord_id().or(playtime.discord_id);
+            playtime.total_seconds += elapsed.num_seconds();
+            *playtime.days.entry(today).or_default() += elapsed.num_seconds();
+            playtime.days.retain(|day, _| *day >= oldest_day);
+        }
+
+        if self
+            .last_saved
+            .is_none_or(|last_saved| now - last_saved >= SAVE_INTERVAL)
+        {
+            self.save();
+        }
+    }
+
+    pub fn players(&self, server_name: &str) -> impl Iterator<Item = &PlayerPlaytime> {
+        self.servers
+            .get(server_name)
+            .into_iter()
+            .flat_map(|players| players.values())
+    }
+}
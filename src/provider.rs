@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use reqwest::Client as HttpClient;
+use serde_json::Value;
+use serenity::async_trait;
+use serenity::prelude::*;
+
+use crate::cfx::CFXStatus;
+use crate::fivem::parse_queue_length;
+use crate::fivem::DynamicInfo;
+use crate::fivem::Player;
+use crate::fivem::ServerInfo;
+
+const CFX_STATUS_URL: &str = "https://status.cfx.re/api/v2/summary.json";
+
+/// Where the status of the FiveM servers and the CFX services comes from
+#[async_trait]
+pub trait StatusProvider: Send + Sync {
+    async fn get_players(&self, endpoint: &str) -> Result<(Box<[Player]>, ServerInfo)>;
+
+    async fn get_dynamic_info(&self, endpoint: &str) -> Result<DynamicInfo>;
+
+    /// The length of the connection queue, read from `field` of the JSON at `url`
+    async fn get_queue_length(&self, url: &str, field: &str) -> Result<u32>;
+
+    async fn get_cfx_status(&self) -> Result<CFXStatus>;
+}
+
+pub struct StatusProviderKey;
+
+impl TypeMapKey for StatusProviderKey {
+    type Value = Arc<dyn StatusProvider>;
+}
+
+/// Fetches the status from the FiveM servers' HTTP endpoints and the CFX status page
+pub struct HttpStatusProvider {
+    http_client: HttpClient,
+}
+
+impl HttpStatusProvider {
+    pub fn new(http_client: HttpClient) -> Self {
+        Self { http_client }
+    }
+}
+
+#[async_trait]
+impl StatusProvider for HttpStatusProvider {
+    async fn get_players(&self, endpoint: &str) -> Result<(Box<[Player]>, ServerInfo)> {
+        let players = self
+            .http_client
+            .get(format!("{}/players.json", endpoint))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Box<[Player]>>()
+            .await?;
+
+        let server_info = self
+            .http_client
+            .get(format!("{}/info.json", endpoint))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ServerInfo>()
+            .await?;
+
+        Ok((players, server_info))
+    }
+
+    async fn get_dynamic_info(&self, endpoint: &str) -> Result<DynamicInfo> {
+        let dynamic_info = self
+            .http_client
+            .get(format!("{}/dynamic.json", endpoint))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DynamicInfo>()
+            .await?;
+
+        Ok(dynamic_info)
+    }
+
+    async fn get_queue_length(&self, url: &str, field: &str) -> Result<u32> {
+        let json = self
+            .http_client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        parse_queue_length(&json, field)
+    }
+
+    async fn get_cfx_status(&self) -> Result<CFXStatus> {
+        let response = self
+            .http_client
+            .get(CFX_STATUS_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<CFXStatus>()
+            .await?;
+
+        Ok(response)
+    }
+}
+
+/// Serves the status from JSON kept in memory, unknown servers are unreachable
+#[cfg(test)]
+#[derive(Default)]
+pub struct InMemoryStatusProvider {
+    /// `players.json` and `info.json` by endpoint
+    servers: std::collections::HashMap<String, (String, String)>,
+    /// `dynamic.json` by endpoint
+    dynamic_infos: std::collections::HashMap<String, String>,
+    /// Queue responses by url
+    queues: std::collections::HashMap<String, String>,
+    /// `summary.json` of the CFX status page
+    cfx_summary: Option<String>,
+}
+
+#[cfg(test)]
+impl InMemoryStatusProvider {
+    pub fn with_server(mut self, endpoint: &str, players_json: &str, info_json: &str) -> Self {
+        self.servers.insert(
+            endpoint.to_string(),
+            (players_json.to_string(), info_json.to_string()),
+        );
+        self
+    }
+
+    pub fn with_dynamic_info(mut self, endpoint: &str, dynamic_json: &str) -> Self {
+        self.dynamic_infos
+            .insert(endpoint.to_string(), dynamic_json.to_string());
+        self
+    }
+
+    pub fn with_queue(mut self, url: &str, queue_json: &str) -> Self {
+        self.queues.insert(url.to_string(), queue_json.to_string());
+        self
+    }
+
+    pub fn with_cfx_summary(mut self, summary_json: &str) -> Self {
+        self.cfx_summary = Some(summary_json.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl StatusProvider for InMemoryStatusProvider {
+    async fn get_players(&self, endpoint: &str) -> Result<(Box<[Player]>, ServerInfo)> {
+        let (players_json, info_json) = self
+            .servers
+            .get(endpoint)
+            .ok_or_else(|| anyhow::anyhow!("`{endpoint}` is unreachable"))?;
+
+        Ok((
+            serde_json::from_str(players_json)?,
+            serde_json::from_str(info_json)?,
+        ))
+    }
+
+    async fn get_dynamic_info(&self, endpoint: &str) -> Result<DynamicInfo> {
+        let dynamic_json = self
+            .dynamic_infos
+            .get(endpoint)
+            .ok_or_else(|| anyhow::anyhow!("`{endpoint}` is unreachable"))?;
+
+        Ok(serde_json::from_str(dynamic_json)?)
+    }
+
+    async fn get_queue_length(&self, url: &str, field: &str) -> Result<u32> {
+        let queue_json = self
+            .queues
+            .get(url)
+            .ok_or_else(|| anyhow::anyhow!("`{url}` is unreachable"))?;
+
+        parse_queue_length(&serde_json::from_str(queue_json)?, field)
+    }
+
+    async fn get_cfx_status(&self) -> Result<CFXStatus> {
+        let summary_json = self
+            .cfx_summary
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("The CFX status page is unreachable"))?;
+
+        Ok(serde_json::from_str(summary_json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+
+    use super::*;
+
+    async fn mock_server() -> MockServer {
+        let server = MockServer::start().await;
+
+        for (route, body) in [
+            (
+                "/players.json",
+                include_str!("../tests/fixtures/players.json"),
+            ),
+            ("/info.json", include_str!("../tests/fixtures/info.json")),
+            (
+                "/dynamic.json",
+                include_str!("../tests/fixtures/dynamic.json"),
+            ),
+            ("/queue", r#"{"queue":["a","b","c"],"connecting":"2"}"#),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(route))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+                .mount(&server)
+                .await;
+        }
+
+        server
+    }
+
+    #[tokio::test]
+    async fn fetches_players_and_info() {
+        let server = mock_server().await;
+        let provider = HttpStatusProvider::new(HttpClient::new());
+
+        let (players, server_info) = provider.get_players(&server.uri()).await.unwrap();
+        assert_eq!(players.len(), 3);
+        assert_eq!(server_info.vars.max_players, "128");
+
+        let dynamic_info = provider.get_dynamic_info(&server.uri()).await.unwrap();
+        assert_eq!(dynamic_info.mapname, "Los Santos");
+    }
+
+    #[tokio::test]
+    async fn reports_http_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/players.json"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_raw("<h1>Bad Gateway</h1>", "text/html"),
+            )
+            .mount(&server)
+            .await;
+        let provider = HttpStatusProvider::new(HttpClient::new());
+
+        let Err(error) = provider.get_players(&server.uri()).await else {
+            panic!("Expected the players request to fail");
+        };
+        let status = error
+            .downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status);
+
+        assert_eq!(status.map(|status| status.as_u16()), Some(503));
+    }
+
+    #[tokio::test]
+    async fn fetches_queue_length() {
+        let server = mock_server().await;
+        let provider = HttpStatusProvider::new(HttpClient::new());
+        let url = format!("{}/queue", server.uri());
+
+        assert_eq!(provider.get_queue_length(&url, "queue").await.unwrap(), 3);
+        assert_eq!(
+            provider
+                .get_queue_length(&url, "/connecting")
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(provider.get_queue_length(&url, "missing").await.is_err());
+
+        let missing_url = format!("{}/no-queue", server.uri());
+        assert!(provider
+            .get_queue_length(&missing_url, "queue")
+            .await
+            .is_err());
+    }
+}
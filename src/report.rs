@@ -0,0 +1,282 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeDelta;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono::Weekday;
+use chrono_tz::Tz;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::Timestamp;
+
+use crate::history::PlayerHistory;
+use crate::playtime::PlaytimeStore;
+use crate::submissions::SubmissionLog;
+use crate::uptime::UptimeLog;
+use crate::utils::format_duration;
+use crate::BRAND_COLOR;
+use crate::BRAND_NAME_SHORT;
+
+/// How long the data of the reports is kept, a bit more than the weekly report covers
+pub const REPORT_RETENTION: TimeDelta = TimeDelta::days(8);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportPeriod {
+    /// Posted every day about the previous day
+    Daily,
+    /// Posted every Monday about the previous week
+    Weekly,
+}
+
+impl FromStr for ReportPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "daily" => Ok(Self::Daily),
+            "weekly" => Ok(Self::Weekly),
+            _ => Err(anyhow!("Unknown report period `{s}`")),
+        }
+    }
+}
+
+impl ReportPeriod {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Daily => "Napi",
+            Self::Weekly => "Heti",
+        }
+    }
+
+    fn days(&self) -> i64 {
+        match self {
+            Self::Daily => 1,
+            Self::Weekly => 7,
+        }
+    }
+
+    fn is_due(&self, date: NaiveDate) -> bool {
+        match self {
+            Self::Daily => true,
+            Self::Weekly => date.weekday() == Weekday::Mon,
+        }
+    }
+}
+
+pub struct ReportSchedule {
+    pub periods: Vec<ReportPeriod>,
+    /// When the reports are posted, in `timezone`
+    pub time: NaiveTime,
+    pub timezone: Tz,
+}
+
+/// The reported period, from the start of its first day to the start of the day of the report
+pub struct ReportWindow {
+    pub period: ReportPeriod,
+    pub from: DateTime<Tz>,
+    pub to: DateTime<Tz>,
+}
+
+impl ReportSchedule {
+    /// When the reports of the given local date are posted
+    pub fn post_time(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        self.timezone
+            .from_local_datetime(&date.and_time(self.time))
+            .earliest()
+            .map(|time| time.with_timezone(&Utc))
+    }
+
+    /// The reports to post on the given local date
+    pub fn due_reports(&self, date: NaiveDate) -> Vec<ReportWindow> {
+        let start_of_day = |date: NaiveDate| {
+            self.timezone
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .earliest()
+        };
+
+        self.periods
+            .iter()
+            .filter(|period| period.is_due(date))
+            .filter_map(|period| {
+                Some(ReportWindow {
+                    period: *period,
+                    from: start_of_day(date - TimeDelta::days(period.days()))?,
+                    to: start_of_day(date)?,
+                })
+            })
+            .collect()
+    }
+}
+
+pub struct ActivityReport {
+    pub peak_players: Option<u32>,
+    pub average_players: Option<f64>,
+    pub unique_players: usize,
+    pub playtime: TimeDelta,
+    pub outages: usize,
+    pub suggestions: usize,
+    pub bug_reports: usize,
+}
+
+impl ActivityReport {
+    pub fn collect(
+        server_name: &str,
+        window: &ReportWindow,
+        history: &PlayerHistory,
+        playtime: &PlaytimeStore,
+        uptime_log: &UptimeLog,
+        submissions: &SubmissionLog,
+    ) -> Self {
+        let from = window.from.with_timezone(&Utc);
+        let to = window.to.with_timezone(&Utc);
+
+        let samples = history.samples_between(server_name, from, to);
+        let average_players = (!samples.is_empty()).then(|| {
+            samples
+                .iter()
+                .map(|sample| sample.players as f64)
+                .sum::<f64>()
+                / samples.len() as f64
+        });
+
+        // Playtime is recorded by UTC day, which is close enough to the local days of the window
+        let (from_date, to_date) = (window.from.date_naive(), window.to.date_naive());
+        let seconds_played: Vec<i64> = playtime
+            .players(server_name)
+            .map(|player| player.seconds_between(from_date, to_date))
+            .filter(|seconds| *seconds > 0)
+            .collect();
+
+        Self {
+            peak_players: samples.iter().map(|sample| sample.players).max(),
+            average_players,
+            unique_players: seconds_played.len(),
+            playtime: TimeDelta::seconds(seconds_played.iter().sum()),
+            outages: uptime_log.stats(server_name, to - from, to).outages,
+            suggestions: submissions.suggestions_between(from, to),
+            bug_reports: submissions.bug_reports_between(from, to),
+        }
+    }
+
+    pub fn embed(&self, server_name: &str, window: &ReportWindow) -> CreateEmbed {
+        let last_day = window.to.date_naive() - TimeDelta::days(1);
+        let dates = match window.period {
+            ReportPeriod::Daily => last_day.format("%Y.%m.%d.").to_string(),
+            ReportPeriod::Weekly => format!(
+                "{} - {}",
+                window.from.format("%Y.%m.%d."),
+                last_day.format("%Y.%m.%d.")
+            ),
+        };
+
+        CreateEmbed::new()
+            .title(format!(
+                "{} | {} Jelentés",
+                server_name,
+                window.period.label()
+            ))
+            .description(format!("**{dates}**"))
+            .field(
+                "Csúcs Játékosszám:",
+                self.peak_players
+                    .map(|peak| peak.to_string())
+                    .unwrap_or(String::from("-")),
+                true,
+            )
+            .field(
+                "Átlagos Játékosszám:",
+                self.average_players
+                    .map(|average| format!("{average:.1}"))
+                    .unwrap_or(String::from("-")),
+                true,
+            )
+            .field("Egyedi Játékosok:", self.unique_players.to_string(), true)
+            .field("Összes Játékidő:", format_duration(self.playtime), true)
+            .field("Leállások:", self.outages.to_string(), true)
+            .field("\u{200b}", "\u{200b}", true)
+            .field("Ötletek:", self.suggestions.to_string(), true)
+            .field("Hibajelentések:", self.bug_reports.to_string(), true)
+            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+            .timestamp(Timestamp::now())
+            .color(BRAND_COLOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uptime::UptimeEvent;
+
+    fn report_schedule(periods: Vec<ReportPeriod>) -> ReportSchedule {
+        ReportSchedule {
+            periods,
+            time: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            timezone: Tz::Europe__Budapest,
+        }
+    }
+
+    #[test]
+    fn posts_weekly_reports_on_mondays() {
+        let schedule = report_schedule(vec![ReportPeriod::Daily, ReportPeriod::Weekly]);
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+
+        let reports = schedule.due_reports(monday);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].period, ReportPeriod::Daily);
+        assert_eq!(reports[0].from.to_rfc3339(), "2026-10-18T00:00:00+02:00");
+        assert_eq!(reports[1].period, ReportPeriod::Weekly);
+        assert_eq!(reports[1].from.to_rfc3339(), "2026-10-12T00:00:00+02:00");
+        assert_eq!(reports[1].to.to_rfc3339(), "2026-10-19T00:00:00+02:00");
+
+        let tuesday = monday + TimeDelta::days(1);
+        assert_eq!(schedule.due_reports(tuesday).len(), 1);
+        assert!(report_schedule(vec![ReportPeriod::Weekly])
+            .due_reports(tuesday)
+            .is_empty());
+    }
+
+    #[test]
+    fn counts_outages_inside_the_window_only() {
+        let schedule = report_schedule(vec![ReportPeriod::Daily]);
+        let window = schedule
+            .due_reports(NaiveDate::from_ymd_opt(2026, 10, 19).unwrap())
+            .remove(0);
+        let from = window.from.with_timezone(&Utc);
+
+        let mut uptime_log = UptimeLog::default();
+        uptime_log.servers.insert(
+            String::from("Main"),
+            [
+                (TimeDelta::hours(-1), true),
+                (TimeDelta::hours(2), false),
+                (TimeDelta::hours(3), true),
+                (TimeDelta::hours(30), false),
+            ]
+            .into_iter()
+            .map(|(offset, online)| UptimeEvent {
+                timestamp: (from + offset).timestamp(),
+                online,
+            })
+            .collect(),
+        );
+
+        let report = ActivityReport::collect(
+            "Main",
+            &window,
+            &PlayerHistory::default(),
+            &PlaytimeStore::default(),
+            &uptime_log,
+            &SubmissionLog::default(),
+        );
+
+        assert_eq!(report.outages, 1);
+        assert_eq!(report.peak_players, None);
+        assert_eq!(report.unique_players, 0);
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeSet;
+
+pub struct ResourceChanges {
+    /// Required resources that stopped running
+    pub missing: Vec<String>,
+    /// Required resources that are running again
+    pub restored: Vec<String>,
+    /// Every required resource that is not running, including the ones missing before
+    pub still_missing: Vec<String>,
+}
+
+/// Compares the running resources of a server against the required ones between status loop ticks
+#[derive(Default)]
+pub struct ResourceMonitor {
+    /// Required resources that were missing at the last check
+    missing: BTreeSet<String>,
+}
+
+impl ResourceMonitor {
+    pub fn update(&mut self, required: &[String], running: &[String]) -> Option<ResourceChanges> {
+        let missing = required
+            .iter()
+            .filter(|resource| !running.contains(resource))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let changes = ResourceChanges {
+            missing: missing.difference(&self.missing).cloned().collect(),
+            restored: self.missing.difference(&missing).cloned().collect(),
+            still_missing: missing.iter().cloned().collect(),
+        };
+
+        self.missing = missing;
+
+        (!changes.missing.is_empty() || !changes.restored.is_empty()).then_some(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_reporting_resources_that_are_still_missing() {
+        let required = names(&["a", "b", "c"]);
+        let mut monitor = ResourceMonitor::default();
+
+        let changes = monitor.update(&required, &names(&["c"])).unwrap();
+        assert_eq!(changes.missing, names(&["a", "b"]));
+        assert_eq!(changes.still_missing, names(&["a", "b"]));
+
+        let changes = monitor.update(&required, &names(&["a", "c"])).unwrap();
+        assert!(changes.missing.is_empty());
+        assert_eq!(changes.restored, names(&["a"]));
+        assert_eq!(changes.still_missing, names(&["b"]));
+
+        let changes = monitor.update(&required, &required).unwrap();
+        assert_eq!(changes.restored, names(&["b"]));
+        assert!(changes.still_missing.is_empty());
+
+        assert!(monitor.update(&required, &required).is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::DateTime;
+use chrono::NaiveTime;
+use chrono::TimeDelta;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono_tz::Tz;
+
+pub struct RestartSchedule {
+    /// Daily restart times, in `timezone`
+    pub times: Vec<NaiveTime>,
+    pub timezone: Tz,
+    /// How many minutes before a restart a warning should be posted
+    pub warning_minutes: Vec<i64>,
+}
+
+impl RestartSchedule {
+    /// Parses a comma separated list of `HH:MM` times, e.g. `06:00,18:00`
+    pub fn parse_times(times: &str) -> Vec<NaiveTime> {
+        times
+            .split(',')
+            .map(str::trim)
+            .filter(|time| !time.is_empty())
+            .map(|time| {
+                NaiveTime::parse_from_str(time, "%H:%M")
+                    .unwrap_or_else(|_| panic!("Expected `{time}` to be a time in `HH:MM` format"))
+            })
+            .collect()
+    }
+
+    pub fn next_restart(&self, now: DateTime<Utc>) -> Option<DateTime<Tz>> {
+        let local_now = now.with_timezone(&self.timezone);
+
+        // Today's and tomorrow's restarts are enough to always find the next one
+        [
+            local_now.date_naive(),
+            local_now.date_naive() + TimeDelta::days(1),
+        ]
+        .iter()
+        .flat_map(|date| {
+            self.times.iter().filter_map(|time| {
+                self.timezone
+                    .from_local_datetime(&date.and_time(*time))
+                    .earliest()
+            })
+        })
+        .filter(|restart| *restart > local_now)
+        .min()
+    }
+
+    pub fn time_until_next_restart(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        self.next_restart(now)
+            .map(|restart| restart.with_timezone(&Utc) - now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    fn schedule(times: &str) -> RestartSchedule {
+        RestartSchedule {
+            times: RestartSchedule::parse_times(times),
+            timezone: Tz::Europe__Budapest,
+            warning_minutes: Vec::new(),
+        }
+    }
+
+    fn utc(local: &str) -> DateTime<Utc> {
+        let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M").unwrap();
+
+        Tz::Europe__Budapest
+            .from_local_datetime(&local)
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(
+            RestartSchedule::parse_times(" 06:00, 18:30 ,"),
+            vec![
+                NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+                NaiveTime::from_hms_opt(18, 30, 0).unwrap()
+            ]
+        );
+        assert!(RestartSchedule::parse_times("").is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "`6am` to be a time")]
+    fn rejects_malformed_times() {
+        RestartSchedule::parse_times("06:00,6am");
+    }
+
+    #[test]
+    fn finds_the_next_restart() {
+        let schedule = schedule("06:00,18:00");
+
+        let next = schedule.next_restart(utc("2026-10-19 12:00")).unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-10-19T18:00:00+02:00");
+
+        // After the last restart of the day it wraps to tomorrow
+        let next = schedule.next_restart(utc("2026-10-19 18:00")).unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-10-20T06:00:00+02:00");
+
+        assert_eq!(
+            schedule.time_until_next_restart(utc("2026-10-19 17:30")),
+            Some(TimeDelta::minutes(30))
+        );
+        assert!(self::schedule("")
+            .next_restart(utc("2026-10-19 12:00"))
+            .is_none());
+    }
+
+    #[test]
+    fn handles_daylight_saving_time() {
+        // 02:30 happens twice when the clocks go back on 2026-10-25, the earlier one is used
+        let next = schedule("02:30")
+            .next_restart(utc("2026-10-25 00:00"))
+            .unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-10-25T02:30:00+02:00");
+
+        // 02:30 doesn't exist when the clocks go forward on 2026-03-29, so that day is skipped
+        let next = schedule("02:30")
+            .next_restart(utc("2026-03-29 00:00"))
+            .unwrap();
+        assert_eq!(next.to_rfc3339(), "2026-03-30T02:30:00+02:00");
+    }
+}
@@ -0,0 +1,602 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use anyhow::Result;
+use chrono::Utc;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use tracing::error;
+
+use crate::config::FivemServer;
+use crate::config::LinkButton;
+use crate::fivem::DynamicInfo;
+use crate::fivem::Player;
+use crate::fivem::ServerInfo;
+use crate::provider::StatusProvider;
+use crate::restart::RestartSchedule;
+use crate::utils::embed_length;
+use crate::utils::format_duration;
+use crate::ERROR_COLOR;
+
+pub fn truncate(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        None => s,
+        Some((idx, _)) => &s[..idx],
+    }
+}
+
+pub fn sanitize_name(name: &str) -> String {
+    name.replace('\\', "\\\\") // for crazy escaping tactics
+        .replace('*', "\\*") // bold, italic
+        .replace('_', "\\_") // underline
+        .replace('~', "\\~") // strikethrough
+        .replace(':', "\\:") // emojis
+        .replace('`', "\\`") // code
+}
+
+/// Discord's limit on the length of an embed field value
+pub const MAX_FIELD_LENGTH: usize = 1024;
+/// Discord's limit on the length of an embed description
+pub const MAX_DESCRIPTION_LENGTH: usize = 4096;
+/// Discord's limit on the combined length of an embed's title, description, fields, footer and author
+pub const MAX_EMBED_LENGTH: usize = 6000;
+/// Room left for the connect field and the footer, which are added after the player list
+const EMBED_LENGTH_RESERVE: usize = 256;
+/// Discord's limit on the number of buttons in an action row
+const MAX_BUTTONS_PER_ROW: usize = 5;
+/// Discord's limit on the number of action rows in a message
+const MAX_ACTION_ROWS: usize = 5;
+
+/// The truncated name of the player, followed by the linked Discord account if there is one
+pub fn player_label(player: &Player) -> String {
+    let name = sanitize_name(truncate(&player.name, 12));
+
+    match player.discord_id() {
+        Some(discord_id) => format!("{} (<@{}>)", name, discord_id),
+        None => name,
+    }
+}
+
+pub fn next_restart_text(restart_schedule: &RestartSchedule) -> String {
+    match restart_schedule.time_until_next_restart(Utc::now()) {
+        Some(remaining) => format_duration(remaining),
+        None => String::from("Nincs ütemezve"),
+    }
+}
+
+/// Details of the server that can be shown in addition to the status and the players
+#[derive(Clone, Copy, PartialEq)]
+pub enum InfoField {
+    Hostname,
+    GameBuild,
+    OneSync,
+    Resources,
+    Tags,
+    Version,
+    Locale,
+    GameType,
+    MapName,
+}
+
+impl FromStr for InfoField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "hostname" => Ok(Self::Hostname),
+            "game_build" => Ok(Self::GameBuild),
+            "onesync" => Ok(Self::OneSync),
+            "resources" => Ok(Self::Resources),
+            "tags" => Ok(Self::Tags),
+            "version" => Ok(Self::Version),
+            "locale" => Ok(Self::Locale),
+            "gametype" => Ok(Self::GameType),
+            "mapname" => Ok(Self::MapName),
+            _ => Err(anyhow!("Unknown server info field `{s}`")),
+        }
+    }
+}
+
+impl InfoField {
+    pub const ALL: [InfoField; 9] = [
+        Self::Hostname,
+        Self::Version,
+        Self::GameBuild,
+        Self::OneSync,
+        Self::Resources,
+        Self::GameType,
+        Self::MapName,
+        Self::Locale,
+        Self::Tags,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hostname => "Szerver Név:",
+            Self::GameBuild => "Játék Build:",
+            Self::OneSync => "OneSync:",
+            Self::Resources => "Resource-ok:",
+            Self::Tags => "Címkék:",
+            Self::Version => "Szerver Verzió:",
+            Self::Locale => "Nyelv:",
+            Self::GameType => "Játékmód:",
+            Self::MapName => "Térkép:",
+        }
+    }
+
+    /// Whether the field is read from `dynamic.json`, which is only fetched when needed
+    fn needs_dynamic_info(self) -> bool {
+        matches!(self, Self::Hostname | Self::GameType | Self::MapName)
+    }
+
+    fn value(self, server_info: &ServerInfo, dynamic_info: Option<&DynamicInfo>) -> Option<String> {
+        let vars = &server_info.vars;
+
+        let value = match self {
+            Self::Hostname => dynamic_info
+                .map(|dynamic_info| strip_color_codes(&dynamic_info.hostname))
+                .or(vars.project_name.clone()),
+            Self::GameBuild => vars.game_build.clone(),
+            Self::OneSync => Some(String::from(
+                match vars.onesync_enabled.as_deref() == Some("true") {
+                    true => "✅ Bekapcsolva",
+                    false => "❌ Kikapcsolva",
+                },
+            )),
+            Self::Resources => Some(server_info.resources.len().to_string()),
+            Self::Tags => vars.tags.clone(),
+            // Only the version number of e.g. `FXServer-master SERVER v1.0.0.7290 linux`
+            Self::Version => server_info.server.as_ref().map(|server| {
+                server
+                    .split_whitespace()
+                    .find(|part| part.starts_with("v1."))
+                    .unwrap_or(server)
+                    .to_string()
+            }),
+            Self::Locale => vars.locale.clone(),
+            Self::GameType => dynamic_info.map(|dynamic_info| dynamic_info.gametype.clone()),
+            Self::MapName => dynamic_info.map(|dynamic_info| dynamic_info.mapname.clone()),
+        };
+
+        value
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| truncate(&sanitize_name(&value), MAX_FIELD_LENGTH).to_string())
+    }
+}
+
+/// Removes FiveM's `^0`-`^9` color codes from a text
+pub fn strip_color_codes(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '^' && chars.peek().is_some_and(char::is_ascii_digit) {
+            chars.next();
+            continue;
+        }
+
+        result.push(c);
+    }
+
+    result.trim().to_string()
+}
+
+/// The length of the connection queue, if the server has a queue endpoint and is reachable
+pub async fn fetch_queue_length(
+    status_provider: &dyn StatusProvider,
+    server: &FivemServer,
+    response: &Result<(Box<[Player]>, ServerInfo)>,
+) -> Option<u32> {
+    let queue_endpoint = server
+        .queue_endpoint
+        .as_ref()
+        .filter(|_| response.is_ok())?;
+    let queue_field = server.queue_field.as_deref().unwrap_or("queue");
+
+    match status_provider
+        .get_queue_length(queue_endpoint, queue_field)
+        .await
+    {
+        Ok(queue_length) => Some(queue_length),
+        Err(e) => {
+            error!("Error getting the queue of `{}`: {e:?}", server.name);
+            None
+        }
+    }
+}
+
+/// Renders the chosen info fields of a reachable server, skipping the ones it doesn't expose
+pub async fn fetch_info_fields(
+    status_provider: &dyn StatusProvider,
+    endpoint: &str,
+    fields: &[InfoField],
+    response: &Result<(Box<[Player]>, ServerInfo)>,
+) -> Vec<(&'static str, String)> {
+    let Ok((_, server_info)) = response else {
+        return Vec::new();
+    };
+
+    let dynamic_info = match fields.iter().any(|field| field.needs_dynamic_info()) {
+        true => status_provider.get_dynamic_info(endpoint).await.ok(),
+        false => None,
+    };
+
+    fields
+        .iter()
+        .filter_map(|field| {
+            field
+                .value(server_info, dynamic_info.as_ref())
+                .map(|value| (field.label(), value))
+        })
+        .collect()
+}
+
+/// Adds the status, player count, next restart, info and player list fields shown in the status message
+pub fn add_server_status_fields(
+    mut embed: CreateEmbed,
+    response: &Result<(Box<[Player]>, ServerInfo)>,
+    next_restart: &str,
+    queue_length: Option<u32>,
+    info_fields: &[(&'static str, String)],
+) -> CreateEmbed {
+    match response {
+        Ok((players, server_info)) => {
+            embed = embed.fields(vec![
+                ("Szerver Státusz:", "✅ Elérhető", true),
+                (
+                    "Elérhető Játékosok:",
+                    &format!("{}/{}", players.len(), server_info.vars.max_players),
+                    true,
+                ),
+            ]);
+            if let Some(queue_length) = queue_length {
+                embed = embed.field("Várólista:", queue_length.to_string(), true);
+            }
+            embed = embed.field("Következő Újraindításig:", next_restart, true);
+            embed = embed.fields(
+                info_fields
+                    .iter()
+                    .map(|(label, value)| (*label, value.as_str(), true)),
+            );
+
+            if !players.is_empty() {
+                let mut player_values = [
+                    String::from("**Játékosok:**\n"),
+                    String::from(""),
+                    String::from(""),
+                ];
+                let mut omitted_players = 0;
+                // The player list gets what's left of the embed's total length, minus the field names
+                let player_list_budget = MAX_EMBED_LENGTH.saturating_sub(
+                    embed_length(&embed) + EMBED_LENGTH_RESERVE + player_values.len(),
+                );
+                let mut player_list_length = player_values[0].len();
+
+                for (i, player) in players.iter().enumerate() {
+                    let line = format!("{} *({}ms)*\n", player_label(player), player.ping);
+                    let value = &mut player_values[(i + 1) % 3];
+
+                    // Leave room in the field and the embed for the omitted players line
+                    if value.len() + line.len() > MAX_FIELD_LENGTH - 64
+                        || player_list_length + line.len() + 64 > player_list_budget
+                    {
+                        omitted_players += 1;
+                        continue;
+                    }
+
+                    player_list_length += line.len();
+                    *value += &line;
+                }
+
+                if omitted_players > 0 {
+                    player_values[0] += &format!("*...és még {} játékos*\n", omitted_players);
+                }
+
+                let player_fields = player_values
+                    .iter()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| ("\u{200b}", s, true));
+
+                embed = embed.fields(player_fields);
+            }
+        }
+        Err(_) => {
+            embed = embed
+                .fields(vec![("Szerver Státusz:", "❌ Nem elérhető", true)])
+                .color(ERROR_COLOR);
+        }
+    }
+
+    embed
+}
+
+/// Adds the console command to join the server, if it has a connect link
+pub fn add_connect_field(embed: CreateEmbed, server: &FivemServer) -> CreateEmbed {
+    match &server.connect_url {
+        Some(url) => {
+            let address = url
+                .trim_start_matches("https://")
+                .trim_start_matches("http://");
+
+            embed.field("Csatlakozás:", format!("`connect {address}`"), false)
+        }
+        None => embed,
+    }
+}
+
+pub fn link_button_rows(buttons: &[LinkButton]) -> Vec<CreateActionRow> {
+    buttons
+        .chunks(MAX_BUTTONS_PER_ROW)
+        .take(MAX_ACTION_ROWS)
+        .map(|buttons| {
+            CreateActionRow::Buttons(
+                buttons
+                    .iter()
+                    .map(|button| CreateButton::new_link(&button.url).label(&button.label))
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serenity::all::CreateEmbed;
+    use serenity::all::CreateEmbedFooter;
+
+    use super::*;
+    use crate::provider::InMemoryStatusProvider;
+    use crate::provider::StatusProvider;
+    use crate::utils::embed_fields;
+
+    const ENDPOINT: &str = "http://127.0.0.1:30120";
+
+    fn provider() -> InMemoryStatusProvider {
+        InMemoryStatusProvider::default().with_server(
+            ENDPOINT,
+            include_str!("../tests/fixtures/players.json"),
+            include_str!("../tests/fixtures/info.json"),
+        )
+    }
+
+    #[tokio::test]
+    async fn renders_online_server() {
+        let response = provider().get_players(ENDPOINT).await;
+        let fields = embed_fields(&add_server_status_fields(
+            CreateEmbed::new(),
+            &response,
+            "6 óra",
+            None,
+            &[],
+        ));
+
+        assert_eq!(fields[0], ("Szerver Státusz:".into(), "✅ Elérhető".into()));
+        assert_eq!(fields[1], ("Elérhető Játékosok:".into(), "3/128".into()));
+        assert_eq!(
+            fields[2],
+            ("Következő Újraindításig:".into(), "6 óra".into())
+        );
+
+        let player_list = fields[3..]
+            .iter()
+            .map(|(_, value)| value.as_str())
+            .collect::<String>();
+
+        assert!(player_list.starts_with("**Játékosok:**\n"));
+        assert!(player_list.contains("Kovács\\_Béla (<@274538119234568192>) *(42ms)*"));
+        assert!(player_list.contains("\\*\\*Nagy Anna\\* *(87ms)*"));
+        assert!(player_list.contains("Szabó Gergel *(120ms)*"));
+    }
+
+    #[tokio::test]
+    async fn renders_offline_server() {
+        let response = provider().get_players("http://127.0.0.1:30130").await;
+        let embed = add_server_status_fields(CreateEmbed::new(), &response, "6 óra", None, &[]);
+
+        assert_eq!(
+            embed_fields(&embed),
+            vec![("Szerver Státusz:".into(), "❌ Nem elérhető".into())]
+        );
+        assert_eq!(
+            serde_json::to_value(&embed).unwrap()["color"],
+            ERROR_COLOR.0
+        );
+    }
+
+    #[tokio::test]
+    async fn caps_player_list_to_field_length() {
+        let players = (1..=300)
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "name": format!("Játékos {id}"),
+                    "ping": 50,
+                    "identifiers": [format!("license:{id}")],
+                })
+            })
+            .collect::<Vec<_>>();
+        let provider = InMemoryStatusProvider::default().with_server(
+            ENDPOINT,
+            &serde_json::to_string(&players).unwrap(),
+            include_str!("../tests/fixtures/info.json"),
+        );
+
+        let response = provider.get_players(ENDPOINT).await;
+        let fields = embed_fields(&add_server_status_fields(
+            CreateEmbed::new(),
+            &response,
+            "6 óra",
+            None,
+            &[],
+        ));
+
+        assert_eq!(fields[1].1, "300/128");
+        assert!(fields
+            .iter()
+            .all(|(_, value)| value.len() <= MAX_FIELD_LENGTH));
+        assert!(fields[3].1.contains("*...és még"));
+    }
+
+    #[tokio::test]
+    async fn caps_player_list_to_embed_length() {
+        let players = (1..=128)
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "name": format!("{id:03} Nagyon_Hosszú_Játékosnév_Ő"),
+                    "ping": 150,
+                    "identifiers": [format!("license:{id}"), format!("discord:{}", 274538119234568192u64 + id)],
+                })
+            })
+            .collect::<Vec<_>>();
+        let provider = InMemoryStatusProvider::default().with_server(
+            ENDPOINT,
+            &serde_json::to_string(&players).unwrap(),
+            include_str!("../tests/fixtures/info.json"),
+        );
+        let info_fields = [
+            ("Szerver Név:", "Smoke Life RolePlay ".repeat(50)),
+            ("Szerver Verzió:", "v1.0.0.7290 ".repeat(80)),
+            ("Resource-ok:", "es_extended, oxmysql, ".repeat(45)),
+            ("Címkék:", "roleplay, magyar, ".repeat(50)),
+        ];
+
+        let response = provider.get_players(ENDPOINT).await;
+        let embed = CreateEmbed::new()
+            .title("Smoke Life RolePlay | Szerver Státusz")
+            .description("A <#1234567890123456789> csatornában mindig értesülsz a szerver aktuális elérhetőségéről és állapotáról!");
+        let embed =
+            add_server_status_fields(embed, &response, "6 óra 15 perc", Some(42), &info_fields)
+                .field(
+                    "Csatlakozás:",
+                    "[Kattints ide](https://cfx.re/join/abc123)",
+                    false,
+                )
+                .footer(CreateEmbedFooter::new("🟢 SLRP"));
+        let fields = embed_fields(&embed);
+
+        assert_eq!(fields[1].1, "128/128");
+        assert!(embed_length(&embed) <= MAX_EMBED_LENGTH);
+        assert!(fields.iter().any(|(_, value)| value.contains("*...és még")));
+    }
+
+    #[tokio::test]
+    async fn renders_selected_info_fields() {
+        let provider =
+            provider().with_dynamic_info(ENDPOINT, include_str!("../tests/fixtures/dynamic.json"));
+
+        let response = provider.get_players(ENDPOINT).await;
+        let info_fields = fetch_info_fields(
+            &provider,
+            ENDPOINT,
+            &[InfoField::Hostname, InfoField::Version, InfoField::OneSync],
+            &response,
+        )
+        .await;
+        let fields = embed_fields(&add_server_status_fields(
+            CreateEmbed::new(),
+            &response,
+            "6 óra",
+            None,
+            &info_fields,
+        ));
+
+        assert_eq!(
+            fields[3..6],
+            [
+                (
+                    "Szerver Név:".into(),
+                    "Smoke Life RolePlay | Magyar roleplay szerver".into()
+                ),
+                ("Szerver Verzió:".into(), "v1.0.0.7290".into()),
+                ("OneSync:".into(), "✅ Bekapcsolva".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_project_name_without_dynamic_info() {
+        let response = provider().get_players(ENDPOINT).await;
+        let info_fields = fetch_info_fields(
+            &provider(),
+            ENDPOINT,
+            &[
+                InfoField::Hostname,
+                InfoField::GameType,
+                InfoField::Resources,
+            ],
+            &response,
+        )
+        .await;
+
+        assert_eq!(
+            info_fields,
+            vec![
+                ("Szerver Név:", String::from("Smoke Life RolePlay")),
+                ("Resource-ok:", String::from("8")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn renders_queue_length() {
+        let server = FivemServer {
+            name: String::from("Smoke Life RolePlay"),
+            endpoint: ENDPOINT.to_string(),
+            status_channel_id: 1,
+            required_resources: Vec::new(),
+            queue_endpoint: Some(String::from("http://127.0.0.1:30120/queue/info")),
+            queue_field: Some(String::from("/queue/count")),
+            connect_url: None,
+        };
+        let provider = provider().with_queue(
+            "http://127.0.0.1:30120/queue/info",
+            r#"{"queue":{"count":14,"priority":3}}"#,
+        );
+
+        let response = provider.get_players(ENDPOINT).await;
+        let queue_length = fetch_queue_length(&provider, &server, &response).await;
+        let fields = embed_fields(&add_server_status_fields(
+            CreateEmbed::new(),
+            &response,
+            "6 óra",
+            queue_length,
+            &[],
+        ));
+
+        assert_eq!(fields[2], ("Várólista:".into(), "14".into()));
+        assert_eq!(fields[3].0, "Következő Újraindításig:");
+    }
+
+    #[test]
+    fn renders_connect_field_and_button_rows() {
+        let server = FivemServer {
+            name: String::from("Smoke Life RolePlay"),
+            endpoint: ENDPOINT.to_string(),
+            status_channel_id: 1,
+            required_resources: Vec::new(),
+            queue_endpoint: None,
+            queue_field: None,
+            connect_url: Some(String::from("https://cfx.re/join/abc123")),
+        };
+
+        let fields = embed_fields(&add_connect_field(CreateEmbed::new(), &server));
+        assert_eq!(
+            fields,
+            vec![("Csatlakozás:".into(), "`connect cfx.re/join/abc123`".into())]
+        );
+
+        let buttons: Vec<LinkButton> = (0..7)
+            .map(|i| LinkButton {
+                label: format!("Link {i}"),
+                url: format!("https://slhungary.com/{i}"),
+            })
+            .collect();
+        let rows = serde_json::to_value(link_button_rows(&buttons)).unwrap();
+
+        assert_eq!(rows[0]["components"].as_array().unwrap().len(), 5);
+        assert_eq!(rows[1]["components"].as_array().unwrap().len(), 2);
+        assert_eq!(rows[1]["components"][1]["label"], "Link 6");
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::prelude::*;
+
+use crate::report::REPORT_RETENTION;
+use crate::utils::load_json_file;
+use crate::utils::save_json_file;
+
+const SUBMISSIONS_FILE: &str = "./submissions.json";
+
+/// When suggestions and bug reports were received, for the activity reports
+#[derive(Serialize, Deserialize, Default)]
+pub struct SubmissionLog {
+    /// Unix timestamps in seconds
+    pub suggestions: Vec<i64>,
+    /// Unix timestamps in seconds
+    #[serde(rename = "bugReports")]
+    pub bug_reports: Vec<i64>,
+}
+
+impl TypeMapKey for SubmissionLog {
+    type Value = Arc<RwLock<SubmissionLog>>;
+}
+
+impl SubmissionLog {
+    pub fn load() -> Self {
+        load_json_file(SUBMISSIONS_FILE)
+    }
+
+    pub fn save(&self) {
+        save_json_file(SUBMISSIONS_FILE, self);
+    }
+
+    pub fn record_suggestion(&mut self, at: DateTime<Utc>) {
+        record(&mut self.suggestions, at);
+        self.save();
+    }
+
+    pub fn record_bug_report(&mut self, at: DateTime<Utc>) {
+        record(&mut self.bug_reports, at);
+        self.save();
+    }
+
+    pub fn suggestions_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> usize {
+        count_between(&self.suggestions, from, to)
+    }
+
+    pub fn bug_reports_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> usize {
+        count_between(&self.bug_reports, from, to)
+    }
+}
+
+fn record(timestamps: &mut Vec<i64>, at: DateTime<Utc>) {
+    let cutoff = (at - REPORT_RETENTION).timestamp();
+
+    timestamps.push(at.timestamp());
+    timestamps.retain(|timestamp| *timestamp >= cutoff);
+}
+
+fn count_between(timestamps: &[i64], from: DateTime<Utc>, to: DateTime<Utc>) -> usize {
+    timestamps
+        .iter()
+        .filter(|timestamp| (from.timestamp()..to.timestamp()).contains(timestamp))
+        .count()
+}
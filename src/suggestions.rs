@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::all::Color;
+use serenity::all::CreateEmbed;
+use serenity::all::Embed;
+use serenity::prelude::*;
+
+use crate::status::truncate;
+use crate::status::MAX_FIELD_LENGTH;
+use crate::utils::load_json_file;
+use crate::utils::save_json_file;
+use crate::BRAND_COLOR;
+use crate::ERROR_COLOR;
+use crate::SUCCESS_COLOR;
+
+const SUGGESTIONS_FILE: &str = "./suggestions.json";
+
+/// The name of the field added to reviewed suggestions
+const STATUS_FIELD: &str = "Státusz:";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionState {
+    Pending,
+    Accepted,
+    Denied,
+    Considered,
+    Implemented,
+}
+
+impl SuggestionState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pending => "Függőben",
+            Self::Accepted => "Elfogadva",
+            Self::Denied => "Elutasítva",
+            Self::Considered => "Megfontolás alatt",
+            Self::Implemented => "Megvalósítva",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Pending => BRAND_COLOR,
+            Self::Accepted => SUCCESS_COLOR,
+            Self::Denied => ERROR_COLOR,
+            Self::Considered => Color::GOLD,
+            Self::Implemented => Color::DARK_GREEN,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StateChange {
+    pub state: SuggestionState,
+    #[serde(rename = "reviewerId")]
+    pub reviewer_id: u64,
+    pub reason: Option<String>,
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Suggestion {
+    #[serde(rename = "authorId")]
+    pub author_id: u64,
+    #[serde(rename = "channelId")]
+    pub channel_id: u64,
+    #[serde(rename = "messageId")]
+    pub message_id: u64,
+    pub state: SuggestionState,
+    /// Every review of the suggestion, oldest first
+    pub history: Vec<StateChange>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SuggestionStore {
+    /// Suggestions by their number
+    pub suggestions: BTreeMap<u16, Suggestion>,
+}
+
+impl TypeMapKey for SuggestionStore {
+    type Value = Arc<RwLock<SuggestionStore>>;
+}
+
+impl SuggestionStore {
+    pub fn load() -> Self {
+        load_json_file(SUGGESTIONS_FILE)
+    }
+
+    pub fn save(&self) {
+        save_json_file(SUGGESTIONS_FILE, self);
+    }
+
+    pub fn add(&mut self, number: u16, author_id: u64, channel_id: u64, message_id: u64) {
+        self.suggestions.insert(
+            number,
+            Suggestion {
+                author_id,
+                channel_id,
+                message_id,
+                state: SuggestionState::Pending,
+                history: Vec::new(),
+            },
+        );
+        self.save();
+    }
+
+    pub fn get(&self, number: u16) -> Option<&Suggestion> {
+        self.suggestions.get(&number)
+    }
+
+    /// Records a review of the suggestion, returning `false` if there is no such suggestion
+    pub fn review(
+        &mut self,
+        number: u16,
+        state: SuggestionState,
+        reviewer_id: u64,
+        reason: Option<String>,
+    ) -> bool {
+        let Some(suggestion) = self.suggestions.get_mut(&number) else {
+            return false;
+        };
+
+        suggestion.state = state;
+        suggestion.history.push(StateChange {
+            state,
+            reviewer_id,
+            reason,
+            timestamp: Utc::now().timestamp(),
+        });
+        self.save();
+
+        true
+    }
+}
+
+/// The suggestion embed recolored and with its status field replaced by the new review
+pub fn reviewed_embed(
+    mut embed: Embed,
+    state: SuggestionState,
+    reviewer_id: u64,
+    reason: Option<&str>,
+) -> CreateEmbed {
+    embed.fields.retain(|field| field.name != STATUS_FIELD);
+
+    let mut status = format!("**{}**\nBírálta: <@{}>", state.label(), reviewer_id);
+    if let Some(reason) = reason {
+        status += &format!("\nIndok: {reason}");
+    }
+
+    CreateEmbed::from(embed)
+        .field(STATUS_FIELD, truncate(&status, MAX_FIELD_LENGTH), false)
+        .color(state.color())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::embed_fields;
+
+    #[test]
+    fn replaces_the_status_field_on_every_review() {
+        let embed: Embed = serde_json::from_value(json!({
+            "title": "Ötlet - #12",
+            "description": "Legyen több autó",
+            "fields": [],
+        }))
+        .unwrap();
+
+        let accepted = reviewed_embed(embed, SuggestionState::Accepted, 1, None);
+        let embed: Embed =
+            serde_json::from_value(serde_json::to_value(&accepted).unwrap()).unwrap();
+        let implemented = reviewed_embed(
+            embed,
+            SuggestionState::Implemented,
+            2,
+            Some("Kész a frissítésben"),
+        );
+
+        assert_eq!(
+            embed_fields(&implemented),
+            vec![(
+                STATUS_FIELD.into(),
+                "**Megvalósítva**\nBírálta: <@2>\nIndok: Kész a frissítésben".into()
+            )]
+        );
+
+        let rendered = serde_json::to_value(&implemented).unwrap();
+        assert_eq!(rendered["color"], Color::DARK_GREEN.0);
+        assert_eq!(rendered["description"], "Legyen több autó");
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serenity::prelude::*;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+use tracing::info;
+
+use crate::backoff::Backoff;
+
+/// Delay before the first restart of a crashed job, doubled on every consecutive crash
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(300);
+/// A job that ran this long before crashing is restarted without waiting for the previous backoff
+const STABLE_RUNTIME: TimeDelta = TimeDelta::minutes(10);
+
+#[derive(Clone)]
+pub struct JobHealth {
+    pub running: bool,
+    /// When the job was last (re)started
+    pub started_at: DateTime<Utc>,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+/// Runs the background jobs of the bot, at most one instance of each, restarting them when they stop
+#[derive(Default)]
+pub struct Supervisor {
+    jobs: Mutex<BTreeMap<String, JobHealth>>,
+    cancellation_token: CancellationToken,
+}
+
+impl TypeMapKey for Supervisor {
+    type Value = Arc<Supervisor>;
+}
+
+impl Supervisor {
+    /// Spawns the job unless one with the same name is already supervised
+    pub async fn spawn<F, Fut>(self: &Arc<Self>, name: &str, job: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        {
+            let mut jobs = self.jobs.lock().await;
+
+            if jobs.contains_key(name) {
+                info!("Job `{name}` is already running");
+                return;
+            }
+
+            jobs.insert(
+                name.to_string(),
+                JobHealth {
+                    running: true,
+                    started_at: Utc::now(),
+                    restarts: 0,
+                    last_error: None,
+                },
+            );
+        }
+
+        let supervisor = self.clone();
+        let name = name.to_string();
+
+        tokio::spawn(async move {
+            let mut backoff = Backoff::new(RESTART_DELAY, MAX_RESTART_DELAY);
+
+            loop {
+                let started_at = Utc::now();
+                let handle = tokio::spawn(job());
+                let abort_handle = handle.abort_handle();
+
+                let result = tokio::select! {
+                    result = handle => result,
+                    _ = supervisor.cancellation_token.cancelled() => {
+                        abort_handle.abort();
+                        supervisor.update(&name, |health| health.running = false).await;
+                        info!("Job `{name}` was cancelled");
+                        return;
+                    }
+                };
+
+                let reason = match result {
+                    Ok(()) => String::from("the job returned"),
+                    Err(e) => e.to_string(),
+                };
+
+                if Utc::now() - started_at >= STABLE_RUNTIME {
+                    backoff.record(true);
+                }
+                backoff.record(false);
+
+                error!(
+                    "Job `{name}` stopped: {reason}, restarting in {:?}",
+                    backoff.delay()
+                );
+                supervisor
+                    .update(&name, |health| {
+                        health.running = false;
+                        health.last_error = Some(reason);
+                    })
+                    .await;
+
+                tokio::select! {
+                    _ = time::sleep(backoff.delay()) => {}
+                    _ = supervisor.cancellation_token.cancelled() => return,
+                }
+
+                supervisor
+                    .update(&name, |health| {
+                        health.running = true;
+                        health.started_at = Utc::now();
+                        health.restarts += 1;
+                    })
+                    .await;
+            }
+        });
+    }
+
+    /// The health of every supervised job by name
+    pub async fn health(&self) -> BTreeMap<String, JobHealth> {
+        self.jobs.lock().await.clone()
+    }
+
+    /// Stops every job, they are not restarted afterwards
+    pub fn shutdown(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    async fn update(&self, name: &str, f: impl FnOnce(&mut JobHealth)) {
+        if let Some(health) = self.jobs.lock().await.get_mut(name) {
+            f(health);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    /// Spawns a job that counts its runs and then behaves like `job`
+    async fn spawn_counted<Fut>(
+        supervisor: &Arc<Supervisor>,
+        name: &str,
+        job: fn() -> Fut,
+    ) -> Arc<AtomicU32>
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+
+        supervisor
+            .spawn(name, move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                job()
+            })
+            .await;
+
+        runs
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn restarts_panicking_jobs_with_backoff() {
+        let supervisor = Arc::new(Supervisor::default());
+        let runs = spawn_counted(&supervisor, "panics", || async { panic!("boom") }).await;
+
+        // Runs at 0s, restarts after 1s and then after 2s more, the next restart is 4s later
+        time::sleep(Duration::from_secs(5)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        let health = &supervisor.health().await["panics"];
+        assert!(!health.running);
+        assert_eq!(health.restarts, 2);
+        assert!(health.last_error.as_ref().unwrap().contains("boom"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn restarts_returning_jobs() {
+        let supervisor = Arc::new(Supervisor::default());
+        let runs = spawn_counted(&supervisor, "returns", || async {}).await;
+
+        time::sleep(Duration::from_millis(1500)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        let health = &supervisor.health().await["returns"];
+        assert_eq!(health.restarts, 1);
+        assert_eq!(health.last_error.as_deref(), Some("the job returned"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_one_instance_and_stops_on_shutdown() {
+        let supervisor = Arc::new(Supervisor::default());
+        let runs = spawn_counted(&supervisor, "job", std::future::pending).await;
+        let duplicate_runs = spawn_counted(&supervisor, "job", std::future::pending).await;
+
+        time::sleep(Duration::from_secs(1)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(duplicate_runs.load(Ordering::SeqCst), 0);
+        assert!(supervisor.health().await["job"].running);
+
+        supervisor.shutdown();
+        time::sleep(Duration::from_secs(60)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let health = &supervisor.health().await["job"];
+        assert!(!health.running);
+        assert_eq!(health.restarts, 0);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::prelude::*;
+
+use crate::utils::load_json_file;
+use crate::utils::save_json_file;
+
+const UPTIME_FILE: &str = "./uptime.json";
+
+/// Events older than this are dropped, except the one that tells the state at the cutoff
+const RETENTION: TimeDelta = TimeDelta::days(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct UptimeEvent {
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub online: bool,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct UptimeLog {
+    /// Only read to migrate from the time a single server was supported
+    #[serde(rename = "events", default, skip_serializing)]
+    pub legacy_events: Vec<UptimeEvent>,
+    /// Events by server name
+    #[serde(default)]
+    pub servers: HashMap<String, Vec<UptimeEvent>>,
+}
+
+impl TypeMapKey for UptimeLog {
+    type Value = Arc<RwLock<UptimeLog>>;
+}
+
+pub struct UptimeStats {
+    /// Share of the time the server was online, `None` if nothing was recorded in the period
+    pub availability: Option<f64>,
+    pub outages: usize,
+    pub longest_outage: Option<TimeDelta>,
+}
+
+impl UptimeLog {
+    /// Loads the log, moving the events of a single server file to the main server
+    pub fn load(main_server_name: &str) -> Self {
+        let mut uptime_log: Self = load_json_file(UPTIME_FILE);
+
+        if !uptime_log.legacy_events.is_empty() {
+            let legacy_events = std::mem::take(&mut uptime_log.legacy_events);
+            uptime_log
+                .servers
+                .entry(main_server_name.to_string())
+                .or_insert(legacy_events);
+            uptime_log.save();
+        }
+
+        uptime_log
+    }
+
+    pub fn save(&self) {
+        save_json_file(UPTIME_FILE, self);
+    }
+
+    /// Records the server state, only persisting it when it differs from the last known state
+    pub fn record(&mut self, server_name: &str, online: bool, at: DateTime<Utc>) {
+        let events = self.servers.entry(server_name.to_string()).or_default();
+
+        if events.last().is_some_and(|last| last.online == online) {
+            return;
+        }
+
+        events.push(UptimeEvent {
+            timestamp: at.timestamp(),
+            online,
+        });
+
+        let cutoff = (at - RETENTION).timestamp();
+        let first_kept = events
+            .iter()
+            .rposition(|event| event.timestamp < cutoff)
+            .unwrap_or(0);
+        events.drain(..first_kept);
+
+        self.save();
+    }
+
+    /// The current state and since when the server has been in it
+    pub fn current_state(&self, server_name: &str) -> Option<(bool, DateTime<Utc>)> {
+        self.events(server_name).last().and_then(|last| {
+            DateTime::from_timestamp(last.timestamp, 0).map(|since| (last.online, since))
+        })
+    }
+
+    pub fn stats(&self, server_name: &str, period: TimeDelta, now: DateTime<Utc>) -> UptimeStats {
+        let events = self.events(server_name);
+        let period_start = (now - period).timestamp();
+        let now = now.timestamp();
+
+        let mut online_seconds = 0;
+        let mut known_seconds = 0;
+        let mut outages = 0;
+        let mut longest_outage = None;
+
+        // Each event lasts until the next one, the last one until now
+        let ends = events
+            .iter()
+            .skip(1)
+            .map(|event| event.timestamp)
+            .chain([now]);
+
+        for (event, end) in events.iter().zip(ends) {
+            // `now` may be in the past, e.g. the end of a reported period
+            if event.timestamp >= now {
+                break;
+            }
+
+            let end = end.min(now);
+            if end <= period_start {
+                continue;
+            }
+
+            let seconds = end - event.timestamp.max(period_start);
+            known_seconds += seconds;
+
+            if event.online {
+                online_seconds += seconds;
+            } else {
+                outages += 1;
+                longest_outage = longest_outage.max(Some(end - event.timestamp));
+            }
+        }
+
+        UptimeStats {
+            availability: (known_seconds > 0).then(|| online_seconds as f64 / known_seconds as f64),
+            outages,
+            longest_outage: longest_outage.map(TimeDelta::seconds),
+        }
+    }
+
+    fn events(&self, server_name: &str) -> &[UptimeEvent] {
+        self.servers
+            .get(server_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER: &str = "Main";
+
+    fn log(now: DateTime<Utc>, events: &[(TimeDelta, bool)]) -> UptimeLog {
+        let mut uptime_log = UptimeLog::default();
+        uptime_log.servers.insert(
+            SERVER.to_string(),
+            events
+                .iter()
+                .map(|(offset, online)| UptimeEvent {
+                    timestamp: (now + *offset).timestamp(),
+                    online: *online,
+                })
+                .collect(),
+        );
+        uptime_log
+    }
+
+    #[test]
+    fn clips_events_to_the_period() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        // Online since before the window, down for an hour inside it
+        let uptime_log = log(
+            now,
+            &[
+                (TimeDelta::hours(-30), true),
+                (TimeDelta::hours(-10), false),
+                (TimeDelta::hours(-9), true),
+            ],
+        );
+
+        let stats = uptime_log.stats(SERVER, TimeDelta::days(1), now);
+
+        assert_eq!(stats.outages, 1);
+        assert_eq!(stats.longest_outage, Some(TimeDelta::hours(1)));
+        assert_eq!(stats.availability, Some(23.0 / 24.0));
+    }
+
+    #[test]
+    fn counts_an_open_outage_until_now() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        // The outage started before the window, so only its part in the window counts as downtime
+        let uptime_log = log(
+            now,
+            &[
+                (TimeDelta::hours(-48), true),
+                (TimeDelta::hours(-36), false),
+            ],
+        );
+
+        let stats = uptime_log.stats(SERVER, TimeDelta::days(1), now);
+
+        assert_eq!(stats.outages, 1);
+        assert_eq!(stats.longest_outage, Some(TimeDelta::hours(36)));
+        assert_eq!(stats.availability, Some(0.0));
+    }
+
+    #[test]
+    fn ignores_events_after_a_past_now() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let uptime_log = log(
+            now,
+            &[
+                (TimeDelta::hours(-12), true),
+                (TimeDelta::hours(-6), false),
+                (TimeDelta::hours(2), true),
+                (TimeDelta::hours(3), false),
+            ],
+        );
+
+        let stats = uptime_log.stats(SERVER, TimeDelta::days(1), now);
+
+        // The outage is still open at `now`, the later events are not counted
+        assert_eq!(stats.outages, 1);
+        assert_eq!(stats.longest_outage, Some(TimeDelta::hours(6)));
+        assert_eq!(stats.availability, Some(0.5));
+    }
+
+    #[test]
+    fn has_no_stats_without_events() {
+        let stats = UptimeLog::default().stats(SERVER, TimeDelta::days(1), Utc::now());
+
+        assert_eq!(stats.outages, 0);
+        assert_eq!(stats.longest_outage, None);
+        assert_eq!(stats.availability, None);
+    }
+}
@@ -1,13 +1,32 @@
+use std::fs;
+use std::time::Duration;
+
+use chrono::TimeDelta;
 use poise::CreateReply;
+use reqwest::Client as HttpClient;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serenity::all::ChannelId;
+use serenity::all::ComponentInteraction;
+use serenity::all::ComponentInteractionCollector;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
 use serenity::all::CreateMessage;
 use serenity::all::EditMessage;
 use serenity::all::Message;
 use serenity::all::ReactionType;
+use serenity::all::Timestamp;
 use serenity::http::Http;
+use serenity::http::HttpError;
+use serenity::Error as SerenityError;
 use tracing::error;
+use tracing::info;
 
 use crate::commands::Context;
+use crate::ERROR_COLOR;
 
 pub async fn send_message(
     http: &Http,
@@ -23,6 +42,17 @@ pub async fn send_message(
     }
 }
 
+/// The reply sent to a user when their request couldn't be handled
+pub fn error_reply(description: &str) -> CreateMessage {
+    CreateMessage::new().embed(
+        CreateEmbed::new()
+            .title("Hiba")
+            .description(description)
+            .timestamp(Timestamp::now())
+            .color(ERROR_COLOR),
+    )
+}
+
 pub async fn delete_message(http: &Http, msg: &Message) {
     if let Err(e) = msg.delete(&http).await {
         error!("Error deleting message: {e:?}");
@@ -44,14 +74,193 @@ pub async fn edit_message(
     }
 }
 
+/// Why a message that the bot keeps track of couldn't be edited
+pub enum EditError {
+    /// The message or its channel was deleted
+    Missing,
+    Other,
+}
+
+/// Discord's error codes for a deleted message and a deleted channel
+const UNKNOWN_MESSAGE: isize = 10008;
+const UNKNOWN_CHANNEL: isize = 10003;
+
+pub async fn edit_tracked_message(
+    http: &Http,
+    channel_id: ChannelId,
+    msg_id: u64,
+    new_msg: EditMessage,
+) -> Result<Message, EditError> {
+    match channel_id.edit_message(&http, msg_id, new_msg).await {
+        Ok(msg) => Ok(msg),
+        Err(SerenityError::Http(HttpError::UnsuccessfulRequest(response)))
+            if matches!(response.error.code, UNKNOWN_MESSAGE | UNKNOWN_CHANNEL) =>
+        {
+            error!("Error editing message: {}", response.error.message);
+            Err(EditError::Missing)
+        }
+        Err(e) => {
+            error!("Error editing message: {e:?}");
+            Err(EditError::Other)
+        }
+    }
+}
+
 pub async fn send_reply(ctx: &Context<'_>, reply: CreateReply) {
     if let Err(e) = ctx.send(reply).await {
         error!("Error sending reply: {e:?}");
     }
 }
 
+/// Sends the first page as an ephemeral reply, with buttons to step through the other pages
+pub async fn paginate_embeds(
+    ctx: &Context<'_>,
+    pages: Vec<CreateEmbed>,
+) -> Result<(), serenity::Error> {
+    let ctx_id = ctx.id();
+    let prev_button_id = format!("{}prev", ctx_id);
+    let next_button_id = format!("{}next", ctx_id);
+
+    let mut reply = CreateReply::default()
+        .embed(pages[0].clone())
+        .ephemeral(true);
+
+    if pages.len() > 1 {
+        reply = reply.components(vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(&prev_button_id).emoji('◀'),
+            CreateButton::new(&next_button_id).emoji('▶'),
+        ])]);
+    }
+
+    ctx.send(reply).await?;
+
+    if pages.len() <= 1 {
+        return Ok(());
+    }
+
+    let mut current_page = 0;
+    while let Some(press) = ComponentInteractionCollector::new(ctx.serenity_context())
+        .filter(move |press| press.data.custom_id.starts_with(&ctx_id.to_string()))
+        .timeout(Duration::from_secs(60 * 5))
+        .await
+    {
+        if press.data.custom_id == next_button_id {
+            current_page = (current_page + 1) % pages.len();
+        } else if press.data.custom_id == prev_button_id {
+            current_page = current_page.checked_sub(1).unwrap_or(pages.len() - 1);
+        } else {
+            continue;
+        }
+
+        respond_to_interaction(
+            &ctx.serenity_context().http,
+            &press,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new().embed(pages[current_page].clone()),
+            ),
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
 pub async fn react_to_message(http: &Http, msg: &Message, reaction: ReactionType) {
     if let Err(e) = msg.react(http, reaction).await {
         error!("Error reacting to message: {e:?}");
     }
 }
+
+pub async fn respond_to_interaction(
+    http: &Http,
+    interaction: &ComponentInteraction,
+    response: CreateInteractionResponse,
+) {
+    if let Err(e) = interaction.create_response(http, response).await {
+        error!("Error responding to interaction: {e:?}");
+    }
+}
+
+pub fn build_http_client(timeout: Duration) -> HttpClient {
+    HttpClient::builder()
+        .timeout(timeout)
+        .build()
+        .expect("Expected to build the HTTP client")
+}
+
+/// Loads a JSON file, falling back to (and creating) the default value if it is missing or invalid
+pub fn load_json_file<T: DeserializeOwned + Serialize + Default>(path: &str) -> T {
+    match fs::read_to_string(path) {
+        Ok(file_contents) => match serde_json::from_str(file_contents.trim()) {
+            Ok(data) => data,
+            Err(_) => {
+                info!("`{path}` is invalid. Creating new `{path}` file");
+                let new = T::default();
+                save_json_file(path, &new);
+                new
+            }
+        },
+        Err(_) => {
+            info!("`{path}` not found. Creating new `{path}` file");
+            let new = T::default();
+            save_json_file(path, &new);
+            new
+        }
+    }
+}
+
+pub fn save_json_file<T: Serialize>(path: &str, data: &T) {
+    serde_json::to_writer(
+        fs::File::create(path).unwrap_or_else(|_| panic!("Expected to create `{path}` file")),
+        data,
+    )
+    .unwrap_or_else(|_| panic!("Expected to write to `{path}` file"));
+}
+
+/// Formats a duration in Hungarian, e.g. `6 óra 15 perc`
+pub fn format_duration(duration: TimeDelta) -> String {
+    let hours = duration.num_hours();
+    let minutes = duration.num_minutes() % 60;
+
+    match (hours, minutes) {
+        (0, 0) => String::from("kevesebb mint 1 perc"),
+        (0, minutes) => format!("{minutes} perc"),
+        (hours, 0) => format!("{hours} óra"),
+        (hours, minutes) => format!("{hours} óra {minutes} perc"),
+    }
+}
+
+/// The length of the embed's texts, as counted against Discord's limit on the total embed length
+pub fn embed_length(embed: &CreateEmbed) -> usize {
+    let embed = serde_json::to_value(embed).expect("Expected the embed to serialize");
+    let text_length = |value: &serde_json::Value| value.as_str().map_or(0, |s| s.chars().count());
+
+    text_length(&embed["title"])
+        + text_length(&embed["description"])
+        + text_length(&embed["footer"]["text"])
+        + text_length(&embed["author"]["name"])
+        + embed["fields"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|field| text_length(&field["name"]) + text_length(&field["value"]))
+            .sum::<usize>()
+}
+
+/// The name and value of every field of the embed, for asserting on rendered embeds
+#[cfg(test)]
+pub fn embed_fields(embed: &CreateEmbed) -> Vec<(String, String)> {
+    let embed = serde_json::to_value(embed).expect("Expected the embed to serialize");
+
+    embed["fields"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|field| {
+            (
+                field["name"].as_str().unwrap_or_default().to_string(),
+                field["value"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,391 @@
+use std::sync::Arc;
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::State;
+use axum::http::header::AUTHORIZATION;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Json;
+use axum::Router;
+use serde::Deserialize;
+use serenity::all::ChannelId;
+use serenity::all::Color;
+use serenity::all::CreateEmbed;
+use serenity::all::CreateEmbedFooter;
+use serenity::all::CreateMessage;
+use serenity::all::Http;
+use serenity::all::Timestamp;
+use subtle::ConstantTimeEq;
+use tokio::net::TcpListener;
+use tracing::error;
+use tracing::info;
+
+use crate::config::WebhookConfig;
+use crate::status::truncate;
+use crate::status::MAX_DESCRIPTION_LENGTH;
+use crate::status::MAX_FIELD_LENGTH;
+use crate::utils::send_message;
+use crate::BRAND_COLOR;
+use crate::BRAND_NAME;
+use crate::BRAND_NAME_SHORT;
+use crate::ERROR_COLOR;
+use crate::WARNING_COLOR;
+
+/// An event pushed by the game server, in the format of txAdmin's events
+#[derive(Deserialize, Debug)]
+#[serde(
+    tag = "type",
+    content = "data",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum WebhookEvent {
+    ScheduledRestart {
+        seconds_remaining: u64,
+        translated_message: Option<String>,
+    },
+    ServerShuttingDown {
+        author: String,
+        message: Option<String>,
+    },
+    PlayerKicked {
+        target_name: Option<String>,
+        author: String,
+        reason: String,
+    },
+    PlayerWarned {
+        target_name: String,
+        author: String,
+        reason: String,
+    },
+    PlayerBanned {
+        target_name: String,
+        author: String,
+        reason: String,
+        /// Not set for permanent bans
+        duration_translated: Option<String>,
+    },
+    Announcement {
+        author: String,
+        message: String,
+    },
+}
+
+impl WebhookEvent {
+    /// The `type` of the event, used to route it to a channel
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ScheduledRestart { .. } => "scheduledRestart",
+            Self::ServerShuttingDown { .. } => "serverShuttingDown",
+            Self::PlayerKicked { .. } => "playerKicked",
+            Self::PlayerWarned { .. } => "playerWarned",
+            Self::PlayerBanned { .. } => "playerBanned",
+            Self::Announcement { .. } => "announcement",
+        }
+    }
+
+    pub fn embed(&self) -> CreateEmbed {
+        let (title, color, embed) = match self {
+            Self::ScheduledRestart {
+                seconds_remaining,
+                translated_message,
+            } => {
+                let remaining = match seconds_remaining {
+                    0..60 => format!("{seconds_remaining} másodperc"),
+                    _ => format!("{} perc", seconds_remaining / 60),
+                };
+                let description = translated_message
+                    .clone()
+                    .unwrap_or(format!("A szerver **{remaining}** múlva újraindul!"));
+
+                (
+                    "Szerver Újraindítás",
+                    WARNING_COLOR,
+                    CreateEmbed::new().description(truncate(&description, MAX_DESCRIPTION_LENGTH)),
+                )
+            }
+            Self::ServerShuttingDown { author, message } => (
+                "Szerver Leállítás",
+                ERROR_COLOR,
+                CreateEmbed::new()
+                    .description(truncate(
+                        message.as_deref().unwrap_or("A szerver leáll!"),
+                        MAX_DESCRIPTION_LENGTH,
+                    ))
+                    .field("Leállította:", field_value(author), true),
+            ),
+            Self::PlayerKicked {
+                target_name,
+                author,
+                reason,
+            } => (
+                "Játékos Kirúgva",
+                WARNING_COLOR,
+                CreateEmbed::new().fields(vec![
+                    (
+                        "Játékos:",
+                        field_value(target_name.as_deref().unwrap_or_default()),
+                        true,
+                    ),
+                    ("Admin:", field_value(author), true),
+                    ("Indok:", truncate(reason, MAX_FIELD_LENGTH), false),
+                ]),
+            ),
+            Self::PlayerWarned {
+                target_name,
+                author,
+                reason,
+            } => (
+                "Játékos Figyelmeztetve",
+                Color::GOLD,
+                CreateEmbed::new().fields(vec![
+                    ("Játékos:", field_value(target_name), true),
+                    ("Admin:", field_value(author), true),
+                    ("Indok:", truncate(reason, MAX_FIELD_LENGTH), false),
+                ]),
+            ),
+            Self::PlayerBanned {
+                target_name,
+                author,
+                reason,
+                duration_translated,
+            } => (
+                "Játékos Kitiltva",
+                ERROR_COLOR,
+                CreateEmbed::new().fields(vec![
+                    ("Játékos:", field_value(target_name), true),
+                    ("Admin:", field_value(author), true),
+                    (
+                        "Időtartam:",
+                        duration_translated.as_deref().unwrap_or("Végleges"),
+                        true,
+                    ),
+                    ("Indok:", truncate(reason, MAX_FIELD_LENGTH), false),
+                ]),
+            ),
+            Self::Announcement { author, message } => (
+                "Bejelentés",
+                BRAND_COLOR,
+                CreateEmbed::new()
+                    .description(truncate(message, MAX_DESCRIPTION_LENGTH))
+                    .field("Bejelentő:", field_value(author), true),
+            ),
+        };
+
+        embed
+            .title(format!("{} | {}", BRAND_NAME, title))
+            .footer(CreateEmbedFooter::new(BRAND_NAME_SHORT))
+            .timestamp(Timestamp::now())
+            .color(color)
+    }
+}
+
+/// A name shown in an embed field, which Discord rejects when empty or too long
+fn field_value(value: &str) -> &str {
+    match value.trim() {
+        "" => "-",
+        _ => truncate(value, MAX_FIELD_LENGTH),
+    }
+}
+
+#[derive(Clone)]
+struct WebhookState {
+    http: Arc<Http>,
+    config: Arc<WebhookConfig>,
+}
+
+pub fn router(http: Arc<Http>, config: WebhookConfig) -> Router {
+    Router::new()
+        .route("/webhook", post(receive_event))
+        .with_state(WebhookState {
+            http,
+            config: Arc::new(config),
+        })
+}
+
+/// Serves the webhook endpoint until the listener fails
+pub async fn serve_webhooks(http: Arc<Http>, config: WebhookConfig) {
+    let listener = match TcpListener::bind(config.address).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!(
+                "Error binding the webhook listener to {}: {e:?}",
+                config.address
+            );
+            return;
+        }
+    };
+
+    info!("Listening for webhooks on {}", config.address);
+
+    if let Err(e) = axum::serve(listener, router(http, config)).await {
+        error!("Error serving webhooks: {e:?}");
+    }
+}
+
+async fn receive_event(
+    State(state): State<WebhookState>,
+    headers: HeaderMap,
+    event: Result<Json<WebhookEvent>, JsonRejection>,
+) -> (StatusCode, String) {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let authorized = token
+        .is_some_and(|token| bool::from(token.as_bytes().ct_eq(state.config.token.as_bytes())));
+
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, String::from("Invalid token"));
+    }
+
+    let event = match event {
+        Ok(Json(event)) => event,
+        Err(rejection) => return (rejection.status(), rejection.body_text()),
+    };
+
+    let channel_id = state.config.channel_id(event.name());
+    let message = CreateMessage::new().embed(event.embed());
+
+    match send_message(&state.http, ChannelId::new(channel_id), message).await {
+        Some(sent_message) => {
+            info!(
+                "Relayed `{}` webhook event with id: {}",
+                event.name(),
+                sent_message.id.to_string()
+            );
+
+            (StatusCode::NO_CONTENT, String::new())
+        }
+        None => (
+            StatusCode::BAD_GATEWAY,
+            String::from("Failed to relay the event"),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use reqwest::Client as HttpClient;
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::embed_fields;
+
+    fn config() -> WebhookConfig {
+        WebhookConfig {
+            address: "127.0.0.1:0".parse().unwrap(),
+            token: String::from("secret"),
+            channel_id: 1,
+            event_channels: HashMap::from([(String::from("playerBanned"), 2)]),
+        }
+    }
+
+    #[test]
+    fn parses_and_routes_events() {
+        let event: WebhookEvent = serde_json::from_value(json!({
+            "type": "playerBanned",
+            "data": {
+                "targetName": "John",
+                "author": "Admin",
+                "reason": "RDM",
+                "durationTranslated": "3 nap"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(config().channel_id(event.name()), 2);
+        assert_eq!(
+            embed_fields(&event.embed()),
+            vec![
+                ("Játékos:".into(), "John".into()),
+                ("Admin:".into(), "Admin".into()),
+                ("Időtartam:".into(), "3 nap".into()),
+                ("Indok:".into(), "RDM".into()),
+            ]
+        );
+
+        let event: WebhookEvent = serde_json::from_value(json!({
+            "type": "scheduledRestart",
+            "data": { "secondsRemaining": 300 }
+        }))
+        .unwrap();
+
+        assert_eq!(config().channel_id(event.name()), 1);
+        let embed = serde_json::to_value(event.embed()).unwrap();
+        assert_eq!(
+            embed["description"],
+            "A szerver **5 perc** múlva újraindul!"
+        );
+    }
+
+    #[test]
+    fn truncates_long_texts() {
+        let event: WebhookEvent = serde_json::from_value(json!({
+            "type": "playerKicked",
+            "data": { "author": "Admin", "reason": "x".repeat(5000) }
+        }))
+        .unwrap();
+        assert_eq!(embed_fields(&event.embed())[2].1.len(), MAX_FIELD_LENGTH);
+
+        let event: WebhookEvent = serde_json::from_value(json!({
+            "type": "playerBanned",
+            "data": { "targetName": "x".repeat(5000), "author": "", "reason": "RDM" }
+        }))
+        .unwrap();
+        let fields = embed_fields(&event.embed());
+        assert_eq!(fields[0].1.len(), MAX_FIELD_LENGTH);
+        assert_eq!(fields[1].1, "-");
+
+        let event: WebhookEvent = serde_json::from_value(json!({
+            "type": "announcement",
+            "data": { "author": "Admin", "message": "x".repeat(5000) }
+        }))
+        .unwrap();
+        let embed = serde_json::to_value(event.embed()).unwrap();
+        assert_eq!(
+            embed["description"].as_str().unwrap().len(),
+            MAX_DESCRIPTION_LENGTH
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unauthorized_and_invalid_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, router(Arc::new(Http::new("")), config())).await
+        });
+
+        let client = HttpClient::new();
+        let announcement = json!({
+            "type": "announcement",
+            "data": { "author": "Admin", "message": "Hello" }
+        });
+
+        let response = client.post(&url).json(&announcement).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .post(&url)
+            .bearer_auth("wrong")
+            .json(&announcement)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401);
+
+        let response = client
+            .post(&url)
+            .bearer_auth("secret")
+            .json(&json!({ "type": "unknown", "data": {} }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 422);
+    }
+}